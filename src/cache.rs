@@ -0,0 +1,33 @@
+//! # Caching
+//! Crawlers keep the downloaded robots.txt files of many origins in a cache that implements `RobotsCache`.
+//! The crate provides the in-memory LRU cache `MemoryRobotsCache`,
+//! and the `FileRobotsStore` that keeps robots.txt files on the disk with the `file-store` feature.
+//! The functions `get_or_fetch` and `get_or_fetch_async` look up a cache entry
+//! and fetch the robots.txt file with any http client on a miss.
+//!
+//! # Example
+//! ```rust,no_run
+//! use robotparser::cache::{get_or_fetch, MemoryRobotsCache};
+//! use robotparser::http::RobotsTxtClient;
+//! use robotparser::service::RobotsTxtService;
+//! use reqwest::blocking::Client;
+//! use url::Url;
+//!
+//! let client = Client::new();
+//! let cache = MemoryRobotsCache::new(1000);
+//! let url = Url::parse("https://www.python.org/about/").unwrap();
+//! let robots_txt = get_or_fetch(&cache, &url, |origin| {
+//!     client.fetch_robots_txt(origin).map(|result| result.get_result())
+//! })
+//! .unwrap();
+//! assert!(robots_txt.can_fetch("*", &url));
+//! ```
+
+mod robots_cache;
+pub use self::robots_cache::{get_or_fetch, get_or_fetch_async, RobotsCache};
+mod memory_robots_cache;
+pub use self::memory_robots_cache::MemoryRobotsCache;
+#[cfg(feature = "file-store")]
+mod file_robots_store;
+#[cfg(feature = "file-store")]
+pub use self::file_robots_store::FileRobotsStore;
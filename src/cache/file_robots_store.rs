@@ -0,0 +1,562 @@
+use crate::cache::{MemoryRobotsCache, RobotsCache};
+use crate::model::{get_content_hash, Clock, EvaluationMode, FetchMetadata, FetchOutcome, FetchPolicy, SystemClock};
+use crate::model::{FetchedRobotsTxt, FetchedRobotsTxtContainer};
+use crate::parser::parse_fetched_robots_txt_with_metadata;
+use crate::user_agent::AgentFamilies;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+use url::{Origin, Url};
+
+const FORMAT_VERSION: &str = "robotparser-store 2";
+const FILE_EXTENSION: &str = "robots";
+const TEMP_FILE_EXTENSION: &str = "tmp";
+const DEFAULT_MEMORY_ENTRIES: usize = 1024;
+
+struct IndexEntry {
+    size: u64,
+    tick: u64,
+}
+
+#[derive(Default)]
+struct Index {
+    files: HashMap<String, IndexEntry>,
+    /// File names ordered from the least recently used to the most recently used.
+    recency: BTreeMap<u64, String>,
+    tick: u64,
+    size: u64,
+}
+
+impl Index {
+    fn touch(&mut self, file_name: &str, size: u64) {
+        self.remove(file_name);
+        self.tick += 1;
+        self.size += size;
+        self.recency.insert(self.tick, file_name.into());
+        self.files
+            .insert(file_name.into(), IndexEntry { size, tick: self.tick });
+    }
+
+    fn remove(&mut self, file_name: &str) -> bool {
+        match self.files.remove(file_name) {
+            Some(entry) => {
+                self.size -= entry.size;
+                self.recency.remove(&entry.tick);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Store of the robots.txt files in a directory that survives restarts of the crawler.
+///
+/// Each origin is saved to its own file with the text, the status code, the headers and the fetch time
+/// of the robots.txt file, together with the outcome of the fetch and the evaluation mode.
+/// The files are written to a temporary file first and then renamed,
+/// so a crash never leaves a partially written file behind.
+/// When the total size of the files exceeds the maximum size, the least recently used files are removed.
+///
+/// Files are parsed into `FetchedRobotsTxt` lazily, when the origin is looked up. The stored outcome is kept,
+/// e.g. an unreachable server stays unreachable since the same time, and the text is parsed with
+/// the `ParserOptions` of the `FetchPolicy` of the store, which also provide the user agent matcher.
+/// The cached copy of an unreachable robots.txt file is not saved.
+///
+/// The most recently used robots.txt files are also kept in memory, so that hot lookups
+/// neither read nor parse the file. Files changed by other processes are not noticed while they stay in memory.
+///
+/// This store is available with the `file-store` feature.
+///
+/// # Example
+/// ```rust
+/// use robotparser::cache::{FileRobotsStore, RobotsCache};
+/// use robotparser::parser::parse_fetched_robots_txt;
+/// use std::sync::Arc;
+/// use url::Url;
+///
+/// let directory = std::env::temp_dir().join("robotparser-doc-store");
+/// let store = FileRobotsStore::open(&directory, 64 * 1024 * 1024).unwrap();
+/// let url = Url::parse("https://example.com/page").unwrap();
+/// let robots_txt = parse_fetched_robots_txt(url.origin(), 200, "User-agent: *\nDisallow: /private").get_result();
+/// store.insert(url.origin(), Arc::new(robots_txt));
+///
+/// let store = FileRobotsStore::open(&directory, 64 * 1024 * 1024).unwrap();
+/// assert!(store.get(&url).is_some());
+/// # store.clear();
+/// ```
+pub struct FileRobotsStore {
+    directory: PathBuf,
+    max_size: u64,
+    policy: FetchPolicy,
+    agent_families: AgentFamilies,
+    clock: Arc<dyn Clock>,
+    index: Mutex<Index>,
+    memory: MemoryRobotsCache,
+    temp_files: AtomicU64,
+}
+
+impl FileRobotsStore {
+    /// Opens the store in the directory, creating the directory if it does not exist.
+    /// The total size of the files in the store is kept below `max_size` bytes.
+    pub fn open(directory: impl AsRef<Path>, max_size: u64) -> io::Result<FileRobotsStore> {
+        let directory = directory.as_ref().to_path_buf();
+        fs::create_dir_all(&directory)?;
+        let mut files = Vec::new();
+        for entry in fs::read_dir(&directory)? {
+            let entry = entry?;
+            let path = entry.path();
+            let file_name = match path.file_name().and_then(|file_name| file_name.to_str()) {
+                Some(file_name) => file_name.to_string(),
+                None => continue,
+            };
+            match path.extension().and_then(|extension| extension.to_str()) {
+                Some(FILE_EXTENSION) => {
+                    let metadata = entry.metadata()?;
+                    let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                    files.push((modified, file_name, metadata.len()));
+                }
+                // Leftovers of writes that were interrupted by a crash.
+                Some(TEMP_FILE_EXTENSION) => {
+                    let _ = fs::remove_file(&path);
+                }
+                _ => {}
+            }
+        }
+        files.sort();
+        let mut index = Index::default();
+        for (_, file_name, size) in files.iter() {
+            index.touch(file_name, *size);
+        }
+        let store = FileRobotsStore {
+            directory,
+            max_size,
+            policy: FetchPolicy::default(),
+            agent_families: AgentFamilies::new(),
+            clock: Arc::new(SystemClock),
+            index: Mutex::new(index),
+            memory: memory_cache(DEFAULT_MEMORY_ENTRIES),
+            temp_files: AtomicU64::new(0),
+        };
+        store.evict(&mut store.lock())?;
+        Ok(store)
+    }
+
+    /// Sets the policy whose `ParserOptions` are used to parse the stored robots.txt files.
+    /// The default policy is `FetchPolicy::default()`.
+    pub fn with_policy(mut self, policy: FetchPolicy) -> FileRobotsStore {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets the fallback chains of crawler families of the loaded robots.txt files.
+    /// By default no fallback chains are declared.
+    pub fn with_agent_families(mut self, agent_families: AgentFamilies) -> FileRobotsStore {
+        self.agent_families = agent_families;
+        self
+    }
+
+    /// Sets how many parsed robots.txt files are kept in memory.
+    /// By default 1024 robots.txt files are kept.
+    pub fn with_memory_entries(mut self, max_entries: usize) -> FileRobotsStore {
+        self.memory = memory_cache(max_entries);
+        self
+    }
+
+    /// Sets the clock used to expire the stored robots.txt files.
+    /// The default clock is `SystemClock`.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> FileRobotsStore {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Returns the directory of the store.
+    pub fn get_directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Returns the maximum total size of the files in the store.
+    pub fn get_max_size(&self) -> u64 {
+        self.max_size
+    }
+
+    /// Returns the total size of the files in the store.
+    pub fn get_size(&self) -> u64 {
+        self.lock().size
+    }
+
+    /// Returns the number of origins in the store.
+    pub fn len(&self) -> usize {
+        self.lock().files.len()
+    }
+
+    /// Checks that the store has no origins.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Saves the robots.txt file of the origin, replacing the previous one.
+    pub fn save(&self, origin: &Origin, robots_txt: &FetchedRobotsTxt) -> io::Result<()> {
+        if !origin.is_tuple() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "opaque origin can not be stored",
+            ));
+        }
+        let content = serialize(origin, robots_txt);
+        let file_name = get_file_name(origin);
+        let temp_path = self.directory.join(format!(
+            "{}.{}.{}.{}",
+            file_name,
+            std::process::id(),
+            self.temp_files.fetch_add(1, Ordering::SeqCst),
+            TEMP_FILE_EXTENSION
+        ));
+        let result = write_file(&temp_path, content.as_bytes())
+            .and_then(|_| fs::rename(&temp_path, self.directory.join(&file_name)));
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        result?;
+        sync_directory(&self.directory);
+        self.memory.remove(origin);
+        let mut index = self.lock();
+        index.touch(&file_name, content.len() as u64);
+        self.evict(&mut index)
+    }
+
+    /// Loads the robots.txt file of the origin.
+    /// Returns `None` if the store has no robots.txt file of the origin.
+    pub fn load(&self, origin: &Origin) -> io::Result<Option<FetchedRobotsTxt>> {
+        if !origin.is_tuple() {
+            return Ok(None);
+        }
+        let file_name = get_file_name(origin);
+        let content = match fs::read_to_string(self.directory.join(&file_name)) {
+            Ok(content) => content,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => {
+                self.lock().remove(&file_name);
+                return Ok(None);
+            }
+            Err(err) => return Err(err),
+        };
+        let (stored_origin, metadata, outcome, body) = deserialize(&content)?;
+        // Different origins may have the same file name.
+        if &stored_origin != origin {
+            return Ok(None);
+        }
+        self.lock().touch(&file_name, content.len() as u64);
+        let mut policy = self.policy.clone();
+        policy = match metadata.get_status_code() {
+            Some(status_code) => policy.with_status_code(status_code, outcome.outcome),
+            None => policy.with_network_error(Some(outcome.outcome)),
+        };
+        if let Some((_, timeout)) = outcome.unreachable {
+            policy = policy.with_unreachable_timeout(timeout);
+        }
+        let mut robots_txt =
+            parse_fetched_robots_txt_with_metadata(stored_origin, metadata, body, &policy).get_result();
+        if let Some((since, _)) = outcome.unreachable {
+            robots_txt = robots_txt.with_unreachable_since(since);
+        }
+        if let Some(evaluation_mode) = outcome.evaluation_mode {
+            robots_txt.set_evaluation_mode(evaluation_mode);
+        }
+        robots_txt.set_agent_families(self.agent_families.clone());
+        Ok(Some(robots_txt))
+    }
+
+    /// Deletes the robots.txt file of the origin.
+    /// Returns false if the store had no robots.txt file of the origin.
+    pub fn delete(&self, origin: &Origin) -> io::Result<bool> {
+        let file_name = get_file_name(origin);
+        self.memory.remove(origin);
+        self.lock().remove(&file_name);
+        match fs::remove_file(self.directory.join(&file_name)) {
+            Ok(()) => Ok(true),
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Removes the least recently used files until the total size does not exceed the maximum size.
+    fn evict(&self, index: &mut Index) -> io::Result<()> {
+        while index.size > self.max_size {
+            let file_name = match index.recency.values().next() {
+                Some(file_name) => file_name.clone(),
+                None => break,
+            };
+            index.remove(&file_name);
+            if let Err(err) = fs::remove_file(self.directory.join(&file_name)) {
+                if err.kind() != io::ErrorKind::NotFound {
+                    return Err(err);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Index> {
+        // The index stays consistent even if a thread panicked while holding the lock.
+        self.index.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl RobotsCache for FileRobotsStore {
+    fn get_by_origin(&self, origin: &Origin) -> Option<Arc<FetchedRobotsTxt>> {
+        self.get_stale_by_origin(origin)
+            .filter(|robots_txt| !robots_txt.is_expired(&*self.clock))
+    }
+
+    fn get_stale_by_origin(&self, origin: &Origin) -> Option<Arc<FetchedRobotsTxt>> {
+        if let Some(robots_txt) = self.memory.get_stale_by_origin(origin) {
+            let file_name = get_file_name(origin);
+            let mut index = self.lock();
+            // The file may have been evicted since the robots.txt file was kept in memory.
+            match index.files.get(&file_name).map(|entry| entry.size) {
+                Some(size) => {
+                    index.touch(&file_name, size);
+                    return Some(robots_txt);
+                }
+                None => {
+                    self.memory.remove(origin);
+                }
+            }
+        }
+        match self.load(origin) {
+            Ok(robots_txt) => robots_txt.map(|robots_txt| {
+                let robots_txt = Arc::new(robots_txt);
+                self.memory.insert(origin.clone(), robots_txt.clone());
+                robots_txt
+            }),
+            Err(_) => {
+                // Files that can not be read are dropped, so that the robots.txt file is fetched again.
+                let _ = self.delete(origin);
+                None
+            }
+        }
+    }
+
+    fn insert(&self, origin: Origin, robots_txt: Arc<FetchedRobotsTxt>) {
+        if self.save(&origin, &robots_txt).is_ok() {
+            self.memory.insert(origin, robots_txt);
+        }
+    }
+
+    fn remove(&self, origin: &Origin) -> Option<Arc<FetchedRobotsTxt>> {
+        let robots_txt = self.get_stale_by_origin(origin);
+        let _ = self.delete(origin);
+        robots_txt
+    }
+
+    fn clear(&self) {
+        self.memory.clear();
+        let mut index = self.lock();
+        for file_name in index.files.keys() {
+            let _ = fs::remove_file(self.directory.join(file_name));
+        }
+        *index = Index::default();
+    }
+}
+
+/// Returns the in-memory layer of the store. Stale robots.txt files are kept in it,
+/// because the store returns them as long as their files exist.
+fn memory_cache(max_entries: usize) -> MemoryRobotsCache {
+    MemoryRobotsCache::new(max_entries).with_max_stale(Duration::MAX)
+}
+
+/// What the stored robots.txt file means for crawling.
+struct StoredOutcome {
+    outcome: FetchOutcome,
+    evaluation_mode: Option<EvaluationMode>,
+    /// Time when the server became unreachable and the unreachable timeout.
+    unreachable: Option<(SystemTime, Duration)>,
+}
+
+/// Returns the name of the file of the origin.
+fn get_file_name(origin: &Origin) -> String {
+    let hash = get_content_hash(origin.ascii_serialization().as_bytes());
+    format!("{:016x}.{}", hash, FILE_EXTENSION)
+}
+
+fn serialize(origin: &Origin, robots_txt: &FetchedRobotsTxt) -> String {
+    let metadata = robots_txt.get_metadata();
+    let fetched_at = metadata
+        .get_fetched_at()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default();
+    let mut content = format!("{}\n", FORMAT_VERSION);
+    content.push_str(&format!("origin {}\n", origin.ascii_serialization()));
+    match metadata.get_status_code() {
+        Some(status_code) => content.push_str(&format!("status {}\n", status_code)),
+        None => content.push_str("status -\n"),
+    }
+    content.push_str(&format!("fetched-at {}\n", serialize_duration(fetched_at)));
+    for (name, value) in metadata.get_headers() {
+        content.push_str(&format!("header {}: {}\n", name, value));
+    }
+    for redirect in metadata.get_redirects() {
+        content.push_str(&format!("redirect {}\n", redirect));
+    }
+    if let Some(final_url) = metadata.get_final_url() {
+        content.push_str(&format!("final-url {}\n", final_url));
+    }
+    if let Some(latency) = metadata.get_latency() {
+        content.push_str(&format!("latency {}\n", serialize_duration(latency)));
+    }
+    if let (Some(body_size), Some(content_hash)) = (metadata.get_body_size(), metadata.get_content_hash()) {
+        content.push_str(&format!("body {} {:016x}\n", body_size, content_hash));
+    }
+    match *robots_txt.get_container() {
+        FetchedRobotsTxtContainer::FetchDenied => content.push_str("outcome disallow-all\n"),
+        FetchedRobotsTxtContainer::FetchFailed => content.push_str("outcome allow-all\n"),
+        FetchedRobotsTxtContainer::Fetched(ref parsed) => {
+            content.push_str("outcome parse\n");
+            let evaluation_mode = match parsed.get_evaluation_mode() {
+                EvaluationMode::Legacy => "legacy",
+                EvaluationMode::Rfc9309 => "rfc9309",
+            };
+            content.push_str(&format!("evaluation-mode {}\n", evaluation_mode));
+        }
+        FetchedRobotsTxtContainer::Unreachable { since, timeout, .. } => {
+            content.push_str("outcome unreachable\n");
+            let since = since.duration_since(SystemTime::UNIX_EPOCH).unwrap_or_default();
+            content.push_str(&format!("unreachable-since {}\n", serialize_duration(since)));
+            content.push_str(&format!("unreachable-timeout {}\n", serialize_duration(timeout)));
+        }
+    }
+    content.push('\n');
+    content.push_str(robots_txt.get_body().unwrap_or_default());
+    content
+}
+
+fn deserialize(content: &str) -> io::Result<(Origin, FetchMetadata, StoredOutcome, &str)> {
+    let invalid_data = || io::Error::new(io::ErrorKind::InvalidData, "invalid robots.txt store file");
+    let (head, body) = content.split_once("\n\n").ok_or_else(invalid_data)?;
+    let mut lines = head.lines();
+    if lines.next() != Some(FORMAT_VERSION) {
+        return Err(invalid_data());
+    }
+    let mut origin = None;
+    let mut status_code = None;
+    let mut fetched_at = None;
+    let mut headers = Vec::new();
+    let mut redirects = Vec::new();
+    let mut final_url = None;
+    let mut latency = None;
+    let mut body_digest = None;
+    let mut outcome = None;
+    let mut evaluation_mode = None;
+    let mut unreachable_since = None;
+    let mut unreachable_timeout = None;
+    for line in lines {
+        let (key, value) = line.split_once(' ').ok_or_else(invalid_data)?;
+        match key {
+            "origin" => origin = Url::parse(value).ok().map(|url| url.origin()),
+            "status" => status_code = Some(value.parse::<u16>().ok()),
+            "fetched-at" => {
+                let since_epoch = deserialize_duration(value).ok_or_else(invalid_data)?;
+                fetched_at = Some(
+                    SystemTime::UNIX_EPOCH
+                        .checked_add(since_epoch)
+                        .ok_or_else(invalid_data)?,
+                );
+            }
+            "header" => headers.push(value.split_once(": ").ok_or_else(invalid_data)?),
+            "redirect" => redirects.push(Url::parse(value).map_err(|_| invalid_data())?),
+            "final-url" => final_url = Some(Url::parse(value).map_err(|_| invalid_data())?),
+            "latency" => latency = Some(deserialize_duration(value).ok_or_else(invalid_data)?),
+            "body" => {
+                let (body_size, content_hash) = value.split_once(' ').ok_or_else(invalid_data)?;
+                let body_size = body_size.parse::<usize>().map_err(|_| invalid_data())?;
+                let content_hash = u64::from_str_radix(content_hash, 16).map_err(|_| invalid_data())?;
+                body_digest = Some((body_size, content_hash));
+            }
+            "outcome" => {
+                outcome = Some(match value {
+                    "parse" => FetchOutcome::Parse,
+                    "allow-all" => FetchOutcome::AllowAll,
+                    "disallow-all" => FetchOutcome::DisallowAll,
+                    "unreachable" => FetchOutcome::Unreachable,
+                    _ => return Err(invalid_data()),
+                })
+            }
+            "evaluation-mode" => {
+                evaluation_mode = Some(match value {
+                    "legacy" => EvaluationMode::Legacy,
+                    "rfc9309" => EvaluationMode::Rfc9309,
+                    _ => return Err(invalid_data()),
+                })
+            }
+            "unreachable-since" => {
+                let since_epoch = deserialize_duration(value).ok_or_else(invalid_data)?;
+                unreachable_since = Some(
+                    SystemTime::UNIX_EPOCH
+                        .checked_add(since_epoch)
+                        .ok_or_else(invalid_data)?,
+                );
+            }
+            "unreachable-timeout" => unreachable_timeout = Some(deserialize_duration(value).ok_or_else(invalid_data)?),
+            _ => return Err(invalid_data()),
+        }
+    }
+    let (origin, status_code, fetched_at, outcome) = match (origin, status_code, fetched_at, outcome) {
+        (Some(origin), Some(status_code), Some(fetched_at), Some(outcome)) => {
+            (origin, status_code, fetched_at, outcome)
+        }
+        _ => return Err(invalid_data()),
+    };
+    let unreachable = match (outcome, unreachable_since, unreachable_timeout) {
+        (FetchOutcome::Unreachable, Some(since), Some(timeout)) => Some((since, timeout)),
+        (FetchOutcome::Unreachable, _, _) => return Err(invalid_data()),
+        _ => None,
+    };
+    let outcome = StoredOutcome {
+        outcome,
+        evaluation_mode,
+        unreachable,
+    };
+    let mut metadata = match status_code {
+        Some(status_code) => FetchMetadata::new(status_code),
+        None => FetchMetadata::without_response(),
+    };
+    for (name, value) in headers {
+        metadata = metadata.with_header(name, value);
+    }
+    metadata = metadata.with_redirects(redirects).with_fetched_at(fetched_at);
+    if let Some(final_url) = final_url {
+        metadata = metadata.with_final_url(final_url);
+    }
+    if let Some(latency) = latency {
+        metadata = metadata.with_latency(latency);
+    }
+    if let Some((body_size, content_hash)) = body_digest {
+        metadata = metadata.with_body_digest(body_size, content_hash);
+    }
+    Ok((origin, metadata, outcome, body))
+}
+
+fn serialize_duration(duration: Duration) -> String {
+    format!("{}.{:09}", duration.as_secs(), duration.subsec_nanos())
+}
+
+fn deserialize_duration(value: &str) -> Option<Duration> {
+    let (secs, nanos) = value.split_once('.')?;
+    Some(Duration::new(secs.parse().ok()?, nanos.parse().ok()?))
+}
+
+fn write_file(path: &Path, content: &[u8]) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    file.write_all(content)?;
+    file.sync_all()
+}
+
+/// Flushes the renamed file entry to the disk. Directories can not be synchronized on some platforms.
+fn sync_directory(directory: &Path) {
+    if let Ok(directory) = File::open(directory) {
+        let _ = directory.sync_all();
+    }
+}
@@ -0,0 +1,223 @@
+use crate::cache::RobotsCache;
+use crate::model::{Clock, FetchedRobotsTxt, SystemClock};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, SystemTime};
+use url::Origin;
+
+struct Entry {
+    robots_txt: Arc<FetchedRobotsTxt>,
+    inserted_at: SystemTime,
+    tick: u64,
+}
+
+#[derive(Default)]
+struct Entries {
+    entries: HashMap<Origin, Entry>,
+    /// Origins ordered from the least recently used to the most recently used.
+    recency: BTreeMap<u64, Origin>,
+    tick: u64,
+}
+
+impl Entries {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn remove(&mut self, origin: &Origin) -> Option<Entry> {
+        let entry = self.entries.remove(origin)?;
+        self.recency.remove(&entry.tick);
+        Some(entry)
+    }
+}
+
+/// In-memory cache of the robots.txt files that evicts the least recently used origins.
+///
+/// An entry expires when the lifetime of the robots.txt file elapses (see `FetchedRobotsTxt::expires_at`),
+/// or when the time to live of the cache elapses after the entry was inserted, whichever comes first.
+/// Expired entries are kept for the maximum staleness and are returned by `RobotsCache::get_stale_by_origin`.
+///
+/// # Example
+/// ```rust
+/// use robotparser::cache::{MemoryRobotsCache, RobotsCache};
+/// use robotparser::parser::parse_fetched_robots_txt;
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use url::Url;
+///
+/// let cache = MemoryRobotsCache::new(1000).with_ttl(Duration::from_secs(3600));
+/// let url = Url::parse("https://example.com/page").unwrap();
+/// let robots_txt = parse_fetched_robots_txt(url.origin(), 200, "User-agent: *\nDisallow: /private").get_result();
+/// cache.insert(url.origin(), Arc::new(robots_txt));
+/// assert!(cache.get(&url).is_some());
+/// ```
+pub struct MemoryRobotsCache {
+    entries: Mutex<Entries>,
+    max_entries: usize,
+    ttl: Option<Duration>,
+    max_stale: Duration,
+    clock: Arc<dyn Clock>,
+}
+
+impl MemoryRobotsCache {
+    /// Creates the cache that holds at most `max_entries` origins.
+    pub fn new(max_entries: usize) -> MemoryRobotsCache {
+        MemoryRobotsCache {
+            entries: Mutex::new(Entries::default()),
+            max_entries,
+            ttl: None,
+            max_stale: Duration::from_secs(0),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Sets the maximum time an entry is kept after it was inserted.
+    /// By default entries are kept for the lifetime of the robots.txt file.
+    pub fn with_ttl(mut self, ttl: Duration) -> MemoryRobotsCache {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Sets how long expired entries are kept and served as stale entries.
+    /// By default expired entries are removed.
+    pub fn with_max_stale(mut self, max_stale: Duration) -> MemoryRobotsCache {
+        self.max_stale = max_stale;
+        self
+    }
+
+    /// Sets the clock used to expire entries.
+    /// The default clock is `SystemClock`.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> MemoryRobotsCache {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Returns the maximum number of origins in the cache.
+    pub fn get_max_entries(&self) -> usize {
+        self.max_entries
+    }
+
+    /// Returns the maximum time an entry is kept after it was inserted.
+    pub fn get_ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+
+    /// Returns how long expired entries are kept and served as stale entries.
+    pub fn get_max_stale(&self) -> Duration {
+        self.max_stale
+    }
+
+    /// Returns the number of origins in the cache, including expired entries that were not evicted yet.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Checks that the cache has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes the entries that are expired for longer than the maximum staleness.
+    pub fn purge_expired(&self) {
+        let now = self.clock.now();
+        let mut entries = self.lock();
+        let expired: Vec<Origin> = entries
+            .entries
+            .iter()
+            .filter(|(_, entry)| self.is_evictable(entry, now))
+            .map(|(origin, _)| origin.clone())
+            .collect();
+        for origin in expired.iter() {
+            entries.remove(origin);
+        }
+    }
+
+    fn expires_at(&self, entry: &Entry) -> SystemTime {
+        let expires_at = entry.robots_txt.expires_at();
+        match self.ttl {
+            Some(ttl) => entry
+                .inserted_at
+                .checked_add(ttl)
+                .map_or(expires_at, |ttl_expires_at| expires_at.min(ttl_expires_at)),
+            None => expires_at,
+        }
+    }
+
+    fn is_evictable(&self, entry: &Entry, now: SystemTime) -> bool {
+        matches!(self.expires_at(entry).checked_add(self.max_stale), Some(evictable_at) if now >= evictable_at)
+    }
+
+    /// Returns the entry of the origin and marks it as the most recently used.
+    /// Evictable entries are removed, and expired entries are returned only if `stale` is true.
+    fn get_entry(&self, origin: &Origin, stale: bool) -> Option<Arc<FetchedRobotsTxt>> {
+        let now = self.clock.now();
+        let mut entries = self.lock();
+        let entry = entries.entries.get(origin)?;
+        if self.is_evictable(entry, now) {
+            entries.remove(origin);
+            return None;
+        }
+        if !stale && now >= self.expires_at(entry) {
+            return None;
+        }
+        let tick = entries.next_tick();
+        let entries = &mut *entries;
+        let entry = entries.entries.get_mut(origin)?;
+        entries.recency.remove(&entry.tick);
+        entries.recency.insert(tick, origin.clone());
+        entry.tick = tick;
+        Some(entry.robots_txt.clone())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Entries> {
+        // The entries stay consistent even if a thread panicked while holding the lock.
+        self.entries.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl RobotsCache for MemoryRobotsCache {
+    fn get_by_origin(&self, origin: &Origin) -> Option<Arc<FetchedRobotsTxt>> {
+        self.get_entry(origin, false)
+    }
+
+    fn get_stale_by_origin(&self, origin: &Origin) -> Option<Arc<FetchedRobotsTxt>> {
+        self.get_entry(origin, true)
+    }
+
+    fn insert(&self, origin: Origin, robots_txt: Arc<FetchedRobotsTxt>) {
+        if self.max_entries == 0 {
+            return;
+        }
+        let inserted_at = self.clock.now();
+        let mut entries = self.lock();
+        entries.remove(&origin);
+        while entries.entries.len() >= self.max_entries {
+            let least_recent = match entries.recency.values().next() {
+                Some(origin) => origin.clone(),
+                None => break,
+            };
+            entries.remove(&least_recent);
+        }
+        let tick = entries.next_tick();
+        entries.recency.insert(tick, origin.clone());
+        entries.entries.insert(
+            origin,
+            Entry {
+                robots_txt,
+                inserted_at,
+                tick,
+            },
+        );
+    }
+
+    fn remove(&self, origin: &Origin) -> Option<Arc<FetchedRobotsTxt>> {
+        self.lock().remove(origin).map(|entry| entry.robots_txt)
+    }
+
+    fn clear(&self) {
+        let mut entries = self.lock();
+        entries.entries.clear();
+        entries.recency.clear();
+    }
+}
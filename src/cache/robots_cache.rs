@@ -0,0 +1,67 @@
+use crate::model::FetchedRobotsTxt;
+use std::future::Future;
+use std::sync::Arc;
+use url::{Origin, Url};
+
+/// Thread-safe storage of the downloaded robots.txt files keyed by origin.
+pub trait RobotsCache: Send + Sync {
+    /// Returns the cached robots.txt file of the origin unless it is missing or expired.
+    fn get_by_origin(&self, origin: &Origin) -> Option<Arc<FetchedRobotsTxt>>;
+
+    /// Returns the cached robots.txt file of the origin even if it is expired,
+    /// as long as the cache still keeps it.
+    /// Expired files are served this way while they are refreshed in the background.
+    /// The default implementation keeps no expired files.
+    fn get_stale_by_origin(&self, origin: &Origin) -> Option<Arc<FetchedRobotsTxt>> {
+        let _ = origin;
+        None
+    }
+
+    /// Stores the robots.txt file of the origin, replacing the previous one.
+    fn insert(&self, origin: Origin, robots_txt: Arc<FetchedRobotsTxt>);
+
+    /// Removes the robots.txt file of the origin and returns it.
+    fn remove(&self, origin: &Origin) -> Option<Arc<FetchedRobotsTxt>>;
+
+    /// Removes all robots.txt files.
+    fn clear(&self);
+
+    /// Returns the cached robots.txt file that applies to the url.
+    /// The origin is derived from the url.
+    fn get(&self, url: &Url) -> Option<Arc<FetchedRobotsTxt>> {
+        self.get_by_origin(&url.origin())
+    }
+}
+
+/// Returns the cached robots.txt file that applies to the url.
+/// On a cache miss the robots.txt file is fetched with the blocking function and stored in the cache.
+pub fn get_or_fetch<C, F, E>(cache: &C, url: &Url, fetch: F) -> Result<Arc<FetchedRobotsTxt>, E>
+where
+    C: RobotsCache + ?Sized,
+    F: FnOnce(Origin) -> Result<FetchedRobotsTxt, E>,
+{
+    let origin = url.origin();
+    if let Some(robots_txt) = cache.get_by_origin(&origin) {
+        return Ok(robots_txt);
+    }
+    let robots_txt = Arc::new(fetch(origin.clone())?);
+    cache.insert(origin, robots_txt.clone());
+    Ok(robots_txt)
+}
+
+/// Returns the cached robots.txt file that applies to the url.
+/// On a cache miss the robots.txt file is fetched with the asynchronous function and stored in the cache.
+pub async fn get_or_fetch_async<C, F, R, E>(cache: &C, url: &Url, fetch: F) -> Result<Arc<FetchedRobotsTxt>, E>
+where
+    C: RobotsCache + ?Sized,
+    F: FnOnce(Origin) -> R,
+    R: Future<Output = Result<FetchedRobotsTxt, E>>,
+{
+    let origin = url.origin();
+    if let Some(robots_txt) = cache.get_by_origin(&origin) {
+        return Ok(robots_txt);
+    }
+    let robots_txt = Arc::new(fetch(origin.clone()).await?);
+    cache.insert(origin, robots_txt.clone());
+    Ok(robots_txt)
+}
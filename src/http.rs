@@ -3,10 +3,15 @@
 //! Now only one library is supported - `reqwest`.
 //! But you can also add support for other libraries.
 
+use crate::model::{FetchPolicy, FetchedRobotsTxt};
 use url::Origin;
 #[cfg(feature = "reqwest")]
 /// Support for reqwest library.
 pub mod reqwest;
+#[cfg(feature = "futures")]
+mod robots_resolver;
+#[cfg(feature = "futures")]
+pub use self::robots_resolver::RobotsResolver;
 
 /// User agent of this crate.
 pub const DEFAULT_USER_AGENT: &str = "robotparser-rs (https://crates.io/crates/robotparser)";
@@ -15,5 +20,36 @@ pub const DEFAULT_USER_AGENT: &str = "robotparser-rs (https://crates.io/crates/r
 /// Must be implemented on http-client.
 pub trait RobotsTxtClient {
     type Result;
+
+    /// Fetches and parses the robots.txt file of the origin.
+    /// The response status code is interpreted with the default `FetchPolicy`.
     fn fetch_robots_txt(&self, origin: Origin) -> Self::Result;
+
+    /// Fetches and parses the robots.txt file of the origin.
+    /// The response status code is interpreted with the passed `FetchPolicy`.
+    /// The default implementation ignores the policy and calls `RobotsTxtClient::fetch_robots_txt`.
+    fn fetch_robots_txt_with_policy(&self, origin: Origin, policy: &FetchPolicy) -> Self::Result {
+        let _ = policy;
+        self.fetch_robots_txt(origin)
+    }
+
+    /// Fetches the robots.txt file of the origin again, sending the conditional headers
+    /// made from the previous result. The response is interpreted with the default `FetchPolicy`.
+    fn revalidate_robots_txt(&self, origin: Origin, previous: &FetchedRobotsTxt) -> Self::Result {
+        self.revalidate_robots_txt_with_policy(origin, previous, &FetchPolicy::default())
+    }
+
+    /// Fetches the robots.txt file of the origin again, sending the conditional headers
+    /// made from the `ETag` and `Last-Modified` headers of the previous result.
+    /// The `304 Not Modified` response keeps the previous model and extends its lifetime.
+    /// The default implementation fetches the robots.txt file unconditionally.
+    fn revalidate_robots_txt_with_policy(
+        &self,
+        origin: Origin,
+        previous: &FetchedRobotsTxt,
+        policy: &FetchPolicy,
+    ) -> Self::Result {
+        let _ = previous;
+        self.fetch_robots_txt_with_policy(origin, policy)
+    }
 }
@@ -1,4 +1,153 @@
 mod sync_reqwest;
+#[allow(unused_imports)]
 pub use self::sync_reqwest::*;
 mod async_reqwest;
 pub use self::async_reqwest::*;
+use crate::http::DEFAULT_USER_AGENT;
+use crate::model::{parse_http_date, Error, ErrorKind, FetchMetadata, FetchPolicy, FetchedRobotsTxt, RedirectPolicy};
+use crate::parser::{parse_fetched_robots_txt_with_metadata, parse_fetched_robots_txt_with_previous, ParseResult};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH, RETRY_AFTER, USER_AGENT};
+use reqwest::header::{AGE, CACHE_CONTROL, CONTENT_TYPE, DATE, ETAG, EXPIRES, LAST_MODIFIED, LOCATION};
+use std::time::{Duration, SystemTime};
+use url::{Origin, Url};
+
+/// Returns the url of the robots.txt file of the origin.
+fn get_robots_txt_url(origin: &Origin) -> Result<Url, Error> {
+    let url = format!("{}/robots.txt", origin.unicode_serialization());
+    Url::parse(&url).map_err(|err| Error {
+        kind: ErrorKind::Url(err),
+    })
+}
+
+/// Returns the headers of the robots.txt request.
+/// The request that revalidates the previous result is made conditional.
+fn get_request_headers(previous: Option<&FetchedRobotsTxt>) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    let _ = headers.insert(USER_AGENT, HeaderValue::from_static(DEFAULT_USER_AGENT));
+    if let Some(previous) = previous {
+        if let Some(etag) = previous.get_etag().and_then(|value| HeaderValue::from_str(value).ok()) {
+            let _ = headers.insert(IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = previous
+            .get_last_modified()
+            .and_then(|value| HeaderValue::from_str(value).ok())
+        {
+            let _ = headers.insert(IF_MODIFIED_SINCE, last_modified);
+        }
+    }
+    headers
+}
+
+/// Response headers that are kept in `FetchMetadata`.
+const STORED_HEADERS: [HeaderName; 8] = [
+    CACHE_CONTROL,
+    EXPIRES,
+    DATE,
+    AGE,
+    ETAG,
+    LAST_MODIFIED,
+    CONTENT_TYPE,
+    RETRY_AFTER,
+];
+
+/// Status codes of the redirects that are followed.
+const REDIRECT_STATUS_CODES: [u16; 5] = [301, 302, 303, 307, 308];
+
+/// The next step after the response of the robots.txt request.
+enum Redirect {
+    /// The redirect to the url is followed.
+    Follow(Url),
+    /// The response is the final one.
+    None,
+    /// The redirect loops or exceeds the maximum number of redirects.
+    Exceeded,
+}
+
+/// Returns the next step after the response that was received from the url.
+/// The chain contains the urls that were redirected before.
+fn get_redirect(
+    origin: &Origin,
+    url: &Url,
+    status_code: u16,
+    headers: &HeaderMap,
+    chain: &[Url],
+    policy: &RedirectPolicy,
+) -> Redirect {
+    if !REDIRECT_STATUS_CODES.contains(&status_code) {
+        return Redirect::None;
+    }
+    let location = headers
+        .get(LOCATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| url.join(value).ok());
+    let location = match location {
+        Some(location) if policy.is_allowed(origin, &location) => location,
+        _ => return Redirect::None,
+    };
+    if location == *url || chain.contains(&location) || chain.len() >= policy.get_max_redirects() as usize {
+        return Redirect::Exceeded;
+    }
+    Redirect::Follow(location)
+}
+
+/// Checks that the redirects that were followed by the http client itself to the final url are allowed.
+/// The default policy accepts them, like the previous versions of this crate.
+/// The client does not report the intermediate hops, so they are counted as a single redirect,
+/// and only the final url is checked against the cross-origin rules of the policy.
+fn is_client_redirect_allowed(origin: &Origin, final_url: &Url, chain: &[Url], policy: &RedirectPolicy) -> bool {
+    if *policy == RedirectPolicy::default() {
+        return true;
+    }
+    policy.is_allowed(origin, final_url)
+        && chain.len() < policy.get_max_redirects() as usize
+        && !chain.contains(final_url)
+}
+
+/// Returns the information about the response that was received from the final url after the redirects.
+fn get_metadata(redirects: &[Url], final_url: &Url, status_code: u16, headers: &HeaderMap) -> FetchMetadata {
+    let mut metadata = FetchMetadata::new(status_code)
+        .with_final_url(final_url.clone())
+        .with_redirects(redirects.to_vec());
+    for name in STORED_HEADERS.iter() {
+        for value in headers.get_all(name).iter() {
+            if let Ok(value) = value.to_str() {
+                metadata = metadata.with_header(name.as_str(), value);
+            }
+        }
+    }
+    metadata
+}
+
+/// Returns the delay of the `Retry-After` header.
+/// The header contains either the number of seconds or the HTTP-date.
+fn get_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let retry_at = parse_http_date(value)?;
+    Some(retry_at.duration_since(SystemTime::now()).unwrap_or_default())
+}
+
+/// Parses the final response of the robots.txt request.
+/// The redirect loop or too many redirects mean that the server is unreachable.
+fn parse_response(
+    origin: Origin,
+    metadata: FetchMetadata,
+    text: &str,
+    redirect_exceeded: bool,
+    policy: &FetchPolicy,
+    previous: Option<&FetchedRobotsTxt>,
+) -> ParseResult<FetchedRobotsTxt> {
+    if redirect_exceeded {
+        let robots_txt = FetchedRobotsTxt::unreachable(metadata, policy.get_unreachable_timeout());
+        return ParseResult::new(match previous {
+            Some(previous) => robots_txt.with_previous(previous),
+            None => robots_txt,
+        });
+    }
+    match previous {
+        Some(previous) => parse_fetched_robots_txt_with_previous(origin, metadata, text, policy, previous),
+        None => parse_fetched_robots_txt_with_metadata(origin, metadata, text, policy),
+    }
+}
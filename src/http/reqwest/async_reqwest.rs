@@ -1,48 +1,179 @@
-use crate::http::{RobotsTxtClient, DEFAULT_USER_AGENT};
+use super::is_client_redirect_allowed;
+use super::{get_metadata, get_redirect, get_request_headers, get_retry_after, get_robots_txt_url};
+use super::{parse_response, Redirect};
+use crate::http::RobotsTxtClient;
 use crate::model::FetchedRobotsTxt;
-use crate::model::{Error, ErrorKind};
-use crate::parser::{parse_fetched_robots_txt, ParseResult};
-use futures::future::ok as future_ok;
-use futures::future::TryFutureExt;
+use crate::model::{Error, ErrorKind, FetchMetadata, FetchPolicy};
+use crate::parser::ParseResult;
 use futures::task::{Context, Poll};
 use futures::Future;
-use reqwest::header::HeaderValue;
-use reqwest::header::USER_AGENT;
-use reqwest::Error as ReqwestError;
 use reqwest::Method;
-use reqwest::{Client, Request};
+use reqwest::{Client, Request, Response};
+use std::mem::replace;
 use std::pin::Pin;
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
 use url::{Origin, Url};
 
-type FetchFuture = Box<dyn Future<Output = Result<(ResponseInfo, String), ReqwestError>>>;
-type BoxFuture = Pin<FetchFuture>;
+type FetchFuture = Box<dyn Future<Output = Result<ParseResult<FetchedRobotsTxt>, Error>> + Send>;
 
 impl RobotsTxtClient for Client {
     type Result = Result<RobotsTxtResponse, Error>;
     fn fetch_robots_txt(&self, origin: Origin) -> Self::Result {
-        let url = format!("{}/robots.txt", origin.unicode_serialization());
-        let url = Url::parse(&url).map_err(|err| Error {
-            kind: ErrorKind::Url(err),
-        })?;
-        let mut request = Request::new(Method::GET, url);
-        let _ = request
-            .headers_mut()
-            .insert(USER_AGENT, HeaderValue::from_static(DEFAULT_USER_AGENT));
-        let response = self.execute(request).and_then(|response| {
-            let response_info = ResponseInfo {
-                status_code: response.status().as_u16(),
-            };
-            response
-                .text()
-                .and_then(|response_text| future_ok((response_info, response_text)))
-        });
-        let response: BoxFuture = Box::pin(response);
-        Ok(RobotsTxtResponse { origin, response })
+        self.fetch_robots_txt_with_policy(origin, &FetchPolicy::default())
+    }
+
+    fn fetch_robots_txt_with_policy(&self, origin: Origin, policy: &FetchPolicy) -> Self::Result {
+        let url = get_robots_txt_url(&origin)?;
+        let response = fetch_with_retries(self.clone(), url, origin.clone(), policy.clone(), None);
+        Ok(RobotsTxtResponse {
+            origin,
+            response: Box::pin(response),
+        })
+    }
+
+    fn revalidate_robots_txt_with_policy(
+        &self,
+        origin: Origin,
+        previous: &FetchedRobotsTxt,
+        policy: &FetchPolicy,
+    ) -> Self::Result {
+        let url = get_robots_txt_url(&origin)?;
+        let previous = Some(previous.clone());
+        let response = fetch_with_retries(self.clone(), url, origin.clone(), policy.clone(), previous);
+        Ok(RobotsTxtResponse {
+            origin,
+            response: Box::pin(response),
+        })
     }
 }
 
 struct ResponseInfo {
     status_code: u16,
+    metadata: FetchMetadata,
+    text: String,
+    redirect_exceeded: bool,
+}
+
+async fn fetch_with_retries(
+    client: Client,
+    url: Url,
+    origin: Origin,
+    policy: FetchPolicy,
+    previous: Option<FetchedRobotsTxt>,
+) -> Result<ParseResult<FetchedRobotsTxt>, Error> {
+    let retry_policy = policy.get_retry_policy();
+    let mut retry = 0;
+    let result = loop {
+        let result = fetch(&client, &url, &origin, &policy, previous.as_ref()).await;
+        let retry_after = match result {
+            Ok((ref response_info, retry_after)) if policy.is_retryable_status_code(response_info.status_code) => {
+                retry_after
+            }
+            Err(ref err) if err.is_transient() => None,
+            _ => break result,
+        };
+        if retry >= retry_policy.get_max_retries() {
+            break result;
+        }
+        retry += 1;
+        sleep(retry_policy.get_backoff(retry, retry_after)).await;
+    };
+    let (metadata, text, redirect_exceeded) = match result {
+        Ok((response_info, _)) => (
+            response_info.metadata,
+            response_info.text,
+            response_info.redirect_exceeded,
+        ),
+        Err(err) => match policy.get_network_error_outcome() {
+            Some(_) if err.is_transient() => (FetchMetadata::without_response(), String::new(), false),
+            _ => return Err(err),
+        },
+    };
+    Ok(parse_response(
+        origin,
+        metadata,
+        &text,
+        redirect_exceeded,
+        &policy,
+        previous.as_ref(),
+    ))
+}
+
+/// Sends the robots.txt request, follows its redirects according to the policy,
+/// and returns the final response with the delay of the `Retry-After` header.
+async fn fetch(
+    client: &Client,
+    url: &Url,
+    origin: &Origin,
+    policy: &FetchPolicy,
+    previous: Option<&FetchedRobotsTxt>,
+) -> Result<(ResponseInfo, Option<Duration>), Error> {
+    let started_at = Instant::now();
+    let mut url = url.clone();
+    let mut redirects = Vec::new();
+    loop {
+        let mut request = Request::new(Method::GET, url.clone());
+        *request.headers_mut() = get_request_headers(previous);
+        let response = client.execute(request).await.map_err(|err| Error {
+            kind: ErrorKind::Http(err),
+        })?;
+        let mut client_redirect_denied = false;
+        if *response.url() != url {
+            // The client followed the redirects itself, the fetch fails closed if the policy does not allow them.
+            client_redirect_denied =
+                !is_client_redirect_allowed(origin, response.url(), &redirects, policy.get_redirect_policy());
+            redirects.push(replace(&mut url, response.url().clone()));
+        }
+        let status_code = response.status().as_u16();
+        let headers = response.headers();
+        let redirect = if client_redirect_denied {
+            Redirect::Exceeded
+        } else {
+            get_redirect(
+                origin,
+                &url,
+                status_code,
+                headers,
+                &redirects,
+                policy.get_redirect_policy(),
+            )
+        };
+        if let Redirect::Follow(location) = redirect {
+            redirects.push(replace(&mut url, location));
+            continue;
+        }
+        let metadata = get_metadata(&redirects, &url, status_code, headers);
+        let retry_after = get_retry_after(headers);
+        let text = read_text(response, policy.get_max_body_size()).await?;
+        let metadata = metadata.with_latency(started_at.elapsed());
+        return Ok((
+            ResponseInfo {
+                status_code,
+                metadata,
+                text,
+                redirect_exceeded: matches!(redirect, Redirect::Exceeded),
+            },
+            retry_after,
+        ));
+    }
+}
+
+/// Reads the body of the response, but stops one byte after the maximum size,
+/// so that the parser can cut off the text that exceeds it.
+async fn read_text(mut response: Response, max_size: usize) -> Result<String, Error> {
+    let mut body = Vec::new();
+    let limit = max_size.saturating_add(1);
+    while body.len() < limit {
+        let chunk = response.chunk().await.map_err(|err| Error {
+            kind: ErrorKind::Http(err),
+        })?;
+        match chunk {
+            Some(chunk) => body.extend_from_slice(&chunk[..chunk.len().min(limit - body.len())]),
+            None => break,
+        }
+    }
+    Ok(String::from_utf8_lossy(&body).into_owned())
 }
 
 /// Future for fetching robots.txt result.
@@ -59,18 +190,10 @@ impl RobotsTxtResponse {
 }
 
 impl Future for RobotsTxtResponse {
-    type Output = Result<ParseResult<FetchedRobotsTxt>, ReqwestError>;
+    type Output = Result<ParseResult<FetchedRobotsTxt>, Error>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
         let self_mut = self.get_mut();
-        let response_pin = self_mut.response.as_mut();
-        match response_pin.poll(cx) {
-            Poll::Ready(Ok((response_info, text))) => {
-                let robots_txt = parse_fetched_robots_txt(self_mut.origin.clone(), response_info.status_code, &text);
-                Poll::Ready(Ok(robots_txt))
-            }
-            Poll::Ready(Err(error)) => Poll::Ready(Err(error)),
-            Poll::Pending => Poll::Pending,
-        }
+        self_mut.response.as_mut().poll(cx)
     }
 }
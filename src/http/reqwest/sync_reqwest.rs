@@ -1,32 +1,150 @@
-use crate::http::{RobotsTxtClient, DEFAULT_USER_AGENT};
+use super::is_client_redirect_allowed;
+use super::{get_metadata, get_redirect, get_request_headers, get_retry_after, get_robots_txt_url};
+use super::{parse_response, Redirect};
+use crate::http::RobotsTxtClient;
 use crate::model::FetchedRobotsTxt;
-use crate::model::{Error, ErrorKind};
-use crate::parser::{parse_fetched_robots_txt, ParseResult};
-use reqwest::blocking::{Client, Request};
-use reqwest::header::HeaderValue;
-use reqwest::header::USER_AGENT;
+use crate::model::{Error, ErrorKind, FetchMetadata, FetchPolicy};
+use crate::parser::ParseResult;
+use reqwest::blocking::{Client, Request, Response as HttpResponse};
 use reqwest::Method;
+use std::io::Read;
+use std::mem::replace;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
 use url::{Origin, Url};
 
+struct Response {
+    status_code: u16,
+    metadata: FetchMetadata,
+    text: String,
+    redirect_exceeded: bool,
+}
+
 impl RobotsTxtClient for Client {
     type Result = Result<ParseResult<FetchedRobotsTxt>, Error>;
     fn fetch_robots_txt(&self, origin: Origin) -> Self::Result {
-        let url = format!("{}/robots.txt", origin.unicode_serialization());
-        let url = Url::parse(&url).map_err(|err| Error {
-            kind: ErrorKind::Url(err),
-        })?;
-        let mut request = Request::new(Method::GET, url);
-        let _ = request
-            .headers_mut()
-            .insert(USER_AGENT, HeaderValue::from_static(DEFAULT_USER_AGENT));
-        let response = self.execute(request).map_err(|err| Error {
+        self.fetch_robots_txt_with_policy(origin, &FetchPolicy::default())
+    }
+
+    fn fetch_robots_txt_with_policy(&self, origin: Origin, policy: &FetchPolicy) -> Self::Result {
+        fetch_with_retries(self, origin, policy, None)
+    }
+
+    fn revalidate_robots_txt_with_policy(
+        &self,
+        origin: Origin,
+        previous: &FetchedRobotsTxt,
+        policy: &FetchPolicy,
+    ) -> Self::Result {
+        fetch_with_retries(self, origin, policy, Some(previous))
+    }
+}
+
+fn fetch_with_retries(
+    client: &Client,
+    origin: Origin,
+    policy: &FetchPolicy,
+    previous: Option<&FetchedRobotsTxt>,
+) -> Result<ParseResult<FetchedRobotsTxt>, Error> {
+    let url = get_robots_txt_url(&origin)?;
+    let retry_policy = policy.get_retry_policy();
+    let mut retry = 0;
+    let result = loop {
+        let result = fetch(client, &url, &origin, policy, previous);
+        let retry_after = match result {
+            Ok((ref response, retry_after)) if policy.is_retryable_status_code(response.status_code) => retry_after,
+            Err(ref err) if err.is_transient() => None,
+            _ => break result,
+        };
+        if retry >= retry_policy.get_max_retries() {
+            break result;
+        }
+        retry += 1;
+        sleep(retry_policy.get_backoff(retry, retry_after));
+    };
+    let (metadata, text, redirect_exceeded) = match result {
+        Ok((response, _)) => (response.metadata, response.text, response.redirect_exceeded),
+        Err(err) => match policy.get_network_error_outcome() {
+            Some(_) if err.is_transient() => (FetchMetadata::without_response(), String::new(), false),
+            _ => return Err(err),
+        },
+    };
+    Ok(parse_response(
+        origin,
+        metadata,
+        &text,
+        redirect_exceeded,
+        policy,
+        previous,
+    ))
+}
+
+/// Sends the robots.txt request, follows its redirects according to the policy,
+/// and returns the final response with the delay of the `Retry-After` header.
+fn fetch(
+    client: &Client,
+    url: &Url,
+    origin: &Origin,
+    policy: &FetchPolicy,
+    previous: Option<&FetchedRobotsTxt>,
+) -> Result<(Response, Option<Duration>), Error> {
+    let started_at = Instant::now();
+    let mut url = url.clone();
+    let mut redirects = Vec::new();
+    loop {
+        let mut request = Request::new(Method::GET, url.clone());
+        *request.headers_mut() = get_request_headers(previous);
+        let response = client.execute(request).map_err(|err| Error {
             kind: ErrorKind::Http(err),
         })?;
+        let mut client_redirect_denied = false;
+        if *response.url() != url {
+            // The client followed the redirects itself, the fetch fails closed if the policy does not allow them.
+            client_redirect_denied =
+                !is_client_redirect_allowed(origin, response.url(), &redirects, policy.get_redirect_policy());
+            redirects.push(replace(&mut url, response.url().clone()));
+        }
         let status_code = response.status().as_u16();
-        let text = response.text().map_err(|err| Error {
-            kind: ErrorKind::Http(err),
-        })?;
-        let robots_txt = parse_fetched_robots_txt(origin, status_code, &text);
-        Ok(robots_txt)
+        let headers = response.headers();
+        let redirect = if client_redirect_denied {
+            Redirect::Exceeded
+        } else {
+            get_redirect(
+                origin,
+                &url,
+                status_code,
+                headers,
+                &redirects,
+                policy.get_redirect_policy(),
+            )
+        };
+        if let Redirect::Follow(location) = redirect {
+            redirects.push(replace(&mut url, location));
+            continue;
+        }
+        let metadata = get_metadata(&redirects, &url, status_code, headers);
+        let retry_after = get_retry_after(headers);
+        let text = read_text(response, policy.get_max_body_size())?;
+        let metadata = metadata.with_latency(started_at.elapsed());
+        return Ok((
+            Response {
+                status_code,
+                metadata,
+                text,
+                redirect_exceeded: matches!(redirect, Redirect::Exceeded),
+            },
+            retry_after,
+        ));
     }
 }
+
+/// Reads the body of the response, but stops one byte after the maximum size,
+/// so that the parser can cut off the text that exceeds it.
+fn read_text(response: HttpResponse, max_size: usize) -> Result<String, Error> {
+    let mut body = Vec::new();
+    let limit = max_size.saturating_add(1) as u64;
+    response.take(limit).read_to_end(&mut body).map_err(|err| Error {
+        kind: ErrorKind::Io(err),
+    })?;
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
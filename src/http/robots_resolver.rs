@@ -0,0 +1,220 @@
+use crate::cache::{MemoryRobotsCache, RobotsCache};
+use crate::http::RobotsTxtClient;
+use crate::model::{Clock, Error, FetchPolicy, FetchedRobotsTxt, SystemClock};
+use crate::parser::ParseResult;
+use futures::future::{BoxFuture, FutureExt, Shared};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use url::{Origin, Url};
+
+/// Number of origins kept by the default cache of the resolver.
+const DEFAULT_MAX_ENTRIES: usize = 10_000;
+/// How long the default cache of the resolver keeps expired robots.txt files in the stale-while-revalidate mode.
+const DEFAULT_MAX_STALE: Duration = Duration::from_secs(24 * 60 * 60);
+
+type SharedFetch = Shared<BoxFuture<'static, Result<Arc<FetchedRobotsTxt>, Arc<Error>>>>;
+type Spawner = Arc<dyn Fn(BoxFuture<'static, ()>) + Send + Sync>;
+
+/// Asynchronous resolver that fetches robots.txt files on demand and keeps them in a cache.
+///
+/// Concurrent requests for the same origin are coalesced into a single fetch,
+/// and all of them receive the same shared result.
+/// Failed fetches are not cached, so the next request fetches the robots.txt file again.
+///
+/// In the stale-while-revalidate mode expired robots.txt files are served from the cache
+/// while a single background task fetches them again, see `RobotsResolver::with_stale_while_revalidate`.
+///
+/// # Example
+/// ```rust,no_run
+/// use robotparser::http::RobotsResolver;
+/// use reqwest::Client;
+/// use url::Url;
+///
+/// # async fn example() {
+/// let resolver = RobotsResolver::new(Client::new());
+/// let url = Url::parse("https://www.python.org/about/").unwrap();
+/// assert!(resolver.can_fetch("*", &url).await.unwrap());
+/// # }
+/// ```
+pub struct RobotsResolver<C> {
+    client: C,
+    policy: FetchPolicy,
+    cache: Arc<dyn RobotsCache>,
+    has_default_cache: bool,
+    in_flight: Arc<Mutex<HashMap<Origin, SharedFetch>>>,
+    spawner: Option<Spawner>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<C, F> RobotsResolver<C>
+where
+    C: RobotsTxtClient<Result = Result<F, Error>>,
+    F: Future<Output = Result<ParseResult<FetchedRobotsTxt>, Error>> + Send + 'static,
+{
+    /// Creates the resolver that fetches robots.txt files with the client.
+    /// The resolver uses the default `FetchPolicy` and an in-memory cache.
+    pub fn new(client: C) -> RobotsResolver<C> {
+        RobotsResolver {
+            client,
+            policy: FetchPolicy::default(),
+            cache: Arc::new(MemoryRobotsCache::new(DEFAULT_MAX_ENTRIES)),
+            has_default_cache: true,
+            in_flight: Arc::new(Mutex::new(HashMap::new())),
+            spawner: None,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Sets the policy used to fetch robots.txt files.
+    pub fn with_policy(mut self, policy: FetchPolicy) -> RobotsResolver<C> {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets the cache of the fetched robots.txt files.
+    /// The cache may be shared with other resolvers.
+    pub fn with_cache(mut self, cache: Arc<dyn RobotsCache>) -> RobotsResolver<C> {
+        self.cache = cache;
+        self.has_default_cache = false;
+        self
+    }
+
+    /// Enables the stale-while-revalidate mode.
+    ///
+    /// Expired robots.txt files that the cache still keeps (see `RobotsCache::get_stale_by_origin`)
+    /// are returned immediately, and the refresh is passed to the spawner to run in the background,
+    /// e.g. `|refresh| { tokio::spawn(refresh); }`.
+    /// The refresh revalidates the robots.txt file with `RobotsTxtClient::revalidate_robots_txt_with_policy`.
+    /// Only one refresh of an origin runs at a time.
+    ///
+    /// If the refresh fails with an error or finds the server unreachable, the previous robots.txt file
+    /// stays in the cache and the failure is recorded in it (see `FetchedRobotsTxt::get_refresh_failures`).
+    /// The next refresh is attempted after the backoff of the retry policy of the `FetchPolicy`.
+    /// Once the cache drops the stale file, the robots.txt file is fetched in the foreground
+    /// and the unreachable-host rules of the `FetchPolicy` apply.
+    ///
+    /// The default cache of the resolver keeps expired robots.txt files for a day in this mode.
+    /// A cache set by `RobotsResolver::with_cache` must keep them itself, otherwise this mode has no effect,
+    /// e.g. `MemoryRobotsCache::new(1000).with_max_stale(Duration::from_secs(3600))`.
+    pub fn with_stale_while_revalidate(
+        mut self,
+        spawner: impl Fn(BoxFuture<'static, ()>) + Send + Sync + 'static,
+    ) -> RobotsResolver<C> {
+        self.spawner = Some(Arc::new(spawner));
+        if self.has_default_cache {
+            self.cache = Arc::new(MemoryRobotsCache::new(DEFAULT_MAX_ENTRIES).with_max_stale(DEFAULT_MAX_STALE));
+        }
+        self
+    }
+
+    /// Sets the clock used to schedule the refreshes and to check the unreachable timeout.
+    /// The default clock is `SystemClock`.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> RobotsResolver<C> {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Returns the cache of the fetched robots.txt files.
+    pub fn get_cache(&self) -> &Arc<dyn RobotsCache> {
+        &self.cache
+    }
+
+    /// Returns the robots.txt file that applies to the url.
+    /// The robots.txt file is fetched unless it is already cached.
+    pub async fn resolve(&self, url: &Url) -> Result<Arc<FetchedRobotsTxt>, Arc<Error>> {
+        let origin = url.origin();
+        if let Some(robots_txt) = self.cache.get_by_origin(&origin) {
+            return Ok(robots_txt);
+        }
+        if let Some(ref spawner) = self.spawner {
+            if let Some(robots_txt) = self.cache.get_stale_by_origin(&origin) {
+                if self.is_refresh_due(&robots_txt) {
+                    if let Ok((refresh, true)) = self.get_fetch(origin, Some(robots_txt.clone())) {
+                        spawner(refresh.map(|_| ()).boxed());
+                    }
+                }
+                return Ok(robots_txt);
+            }
+        }
+        self.get_fetch(origin, None)?.0.await
+    }
+
+    /// Fetches the robots.txt file that applies to the url if needed,
+    /// and decides if the user agent can fetch the url.
+    pub async fn can_fetch(&self, user_agent: &str, url: &Url) -> Result<bool, Arc<Error>> {
+        let robots_txt = self.resolve(url).await?;
+        Ok(robots_txt.can_fetch_at(user_agent, url, &*self.clock))
+    }
+
+    /// Checks that the backoff after the last failed refresh of the robots.txt file elapsed.
+    fn is_refresh_due(&self, robots_txt: &FetchedRobotsTxt) -> bool {
+        match robots_txt.get_last_refresh_failure() {
+            Some(failed_at) => {
+                let backoff = self
+                    .policy
+                    .get_retry_policy()
+                    .get_backoff(robots_txt.get_refresh_failures(), None);
+                match failed_at.checked_add(backoff) {
+                    Some(refresh_at) => self.clock.now() >= refresh_at,
+                    None => true,
+                }
+            }
+            None => true,
+        }
+    }
+
+    /// Returns the fetch of the origin that is in flight, or starts a new one.
+    /// The flag is true if a new fetch was started.
+    ///
+    /// If the previous robots.txt file is passed, the fetch revalidates it
+    /// and keeps it when the refresh fails.
+    fn get_fetch(
+        &self,
+        origin: Origin,
+        previous: Option<Arc<FetchedRobotsTxt>>,
+    ) -> Result<(SharedFetch, bool), Arc<Error>> {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(fetch) = in_flight.get(&origin) {
+            return Ok((fetch.clone(), false));
+        }
+        // The fetch may have completed after the cache was checked.
+        if let Some(robots_txt) = self.cache.get_by_origin(&origin) {
+            return Ok((futures::future::ready(Ok(robots_txt)).boxed().shared(), false));
+        }
+        let response = match previous {
+            Some(ref previous) => self
+                .client
+                .revalidate_robots_txt_with_policy(origin.clone(), previous, &self.policy),
+            None => self.client.fetch_robots_txt_with_policy(origin.clone(), &self.policy),
+        }
+        .map_err(Arc::new)?;
+        let cache = self.cache.clone();
+        let clock = self.clock.clone();
+        let fetches = self.in_flight.clone();
+        let fetch_origin = origin.clone();
+        let fetch = async move {
+            let result = match (response.await.map(|result| result.get_result()), previous) {
+                (Ok(robots_txt), Some(ref previous)) if robots_txt.is_unreachable() => {
+                    Ok(Arc::new(previous.with_refresh_failure(clock.now())))
+                }
+                (Ok(robots_txt), _) => Ok(Arc::new(robots_txt)),
+                (Err(_), Some(ref previous)) => Ok(Arc::new(previous.with_refresh_failure(clock.now()))),
+                (Err(err), None) => Err(Arc::new(err)),
+            };
+            if let Ok(ref robots_txt) = result {
+                cache.insert(fetch_origin.clone(), robots_txt.clone());
+            }
+            fetches
+                .lock()
+                .unwrap_or_else(|err| err.into_inner())
+                .remove(&fetch_origin);
+            result
+        }
+        .boxed()
+        .shared();
+        in_flight.insert(origin, fetch.clone());
+        Ok((fetch, true))
+    }
+}
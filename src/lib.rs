@@ -3,6 +3,9 @@
 //! The robots.txt Exclusion Protocol is implemented as specified in
 //! <https://www.robotstxt.org/norobots-rfc.txt>
 //!
+//! The evaluation rules of RFC 9309 <https://www.rfc-editor.org/rfc/rfc9309> can be enabled
+//! with `robotparser::model::EvaluationMode::Rfc9309`.
+//!
 //! # Installation
 //!
 //! Add it to your ``Cargo.toml``:
@@ -28,6 +31,8 @@
 //! assert!(robots_txt.can_fetch("*", &fetch_url));
 //! ```
 
+/// Contains caches of downloaded robots.txt files.
+pub mod cache;
 /// Request builder & response parsers for other http libraries.
 pub mod http;
 /// Contains models of robots.txt file.
@@ -36,3 +41,5 @@ pub mod model;
 pub mod parser;
 /// Contains robots.txt services.
 pub mod service;
+/// Contains user agent analysis utilities.
+pub mod user_agent;
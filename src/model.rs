@@ -1,11 +1,13 @@
 mod path_pattern;
 pub(crate) use self::path_pattern::PathPattern;
 mod group;
-pub(crate) use self::group::Group;
+pub use self::group::Group;
 mod rule;
-pub(crate) use self::rule::Rule;
+pub use self::rule::Rule;
+mod rule_index;
+pub(crate) use self::rule_index::RuleMatch;
 mod clean_params;
-pub(crate) use self::clean_params::CleanParams;
+pub use self::clean_params::CleanParams;
 mod request_rate;
 pub use self::request_rate::RequestRate;
 mod robots_txt;
@@ -14,6 +16,31 @@ pub(crate) use self::fetched_robots_txt::FetchedRobotsTxtContainer;
 mod fetched_robots_txt;
 pub use self::robots_txt::RobotsTxt;
 mod path;
-pub(crate) use self::path::Path;
+pub use self::path::{canonicalize_url, Path};
 mod errors;
-pub use self::errors::{Error, ErrorKind};
+mod percent_encoding_normalizer;
+pub use self::errors::{Error, ErrorClass, ErrorKind};
+mod evaluation_mode;
+pub use self::evaluation_mode::EvaluationMode;
+mod user_agent_matcher;
+pub use self::user_agent_matcher::{PrefixMatcher, ProductTokenMatcher, SubstringMatcher, UserAgentMatcher};
+mod fetch_policy;
+pub use self::fetch_policy::{FetchOutcome, FetchPolicy, StatusClass};
+mod retry_policy;
+pub use self::retry_policy::RetryPolicy;
+mod http_date;
+pub(crate) use self::http_date::parse_http_date;
+mod clock;
+pub use self::clock::{Clock, SystemClock};
+mod fetch_metadata;
+pub use self::fetch_metadata::FetchMetadata;
+mod fetch_status;
+pub use self::fetch_status::FetchStatus;
+mod content_hash;
+pub(crate) use self::content_hash::get_content_hash;
+mod redirect_policy;
+pub use self::redirect_policy::{CrossOriginRedirects, RedirectPolicy};
+mod decision;
+pub use self::decision::{Decision, DecisionReason};
+mod source_span;
+pub use self::source_span::SourceSpan;
@@ -1,21 +1,44 @@
+use crate::model::source_span::SourceSpan;
 use crate::model::PathPattern;
 
+/// Parameters of the `Clean-param` directive.
 #[derive(Debug, Clone)]
 pub struct CleanParams {
     path_pattern: PathPattern,
     params: Vec<String>,
+    span: Option<SourceSpan>,
 }
 
 impl CleanParams {
-    pub fn new(path_pattern: PathPattern, params: Vec<String>) -> CleanParams {
-        CleanParams { path_pattern, params }
+    pub(crate) fn new(path_pattern: PathPattern, params: Vec<String>) -> CleanParams {
+        CleanParams {
+            path_pattern,
+            params,
+            span: None,
+        }
     }
 
-    pub fn get_path_pattern(&self) -> &PathPattern {
+    pub(crate) fn with_span(mut self, span: SourceSpan) -> CleanParams {
+        self.span = Some(span);
+        self
+    }
+
+    pub(crate) fn get_path_pattern(&self) -> &PathPattern {
         &self.path_pattern
     }
 
+    /// Returns the path pattern of the urls whose parameters are removed.
+    pub fn get_pattern(&self) -> &str {
+        self.path_pattern.as_str()
+    }
+
+    /// Returns the names of the parameters that are removed.
     pub fn get_params(&self) -> &Vec<String> {
         &self.params
     }
+
+    /// Returns the location of the directive in the robots.txt file.
+    pub fn get_span(&self) -> Option<SourceSpan> {
+        self.span
+    }
 }
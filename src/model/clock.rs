@@ -0,0 +1,18 @@
+use std::time::SystemTime;
+
+/// Source of the current time.
+/// Time-dependent decisions take a clock, so that they can be tested without waiting.
+pub trait Clock: Send + Sync {
+    /// Returns the current system time.
+    fn now(&self) -> SystemTime;
+}
+
+/// Clock that returns `SystemTime::now()`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
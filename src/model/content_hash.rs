@@ -0,0 +1,10 @@
+/// Returns the 64-bit FNV-1a hash of the bytes.
+/// Unlike the hasher of the standard library, the hash is stable between builds and platforms.
+pub(crate) fn get_content_hash(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
@@ -0,0 +1,103 @@
+use crate::model::fetch_status::FetchStatus;
+use crate::model::group::Group;
+use crate::model::rule::Rule;
+use crate::model::source_span::SourceSpan;
+
+/// What decided whether the url can be fetched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecisionReason {
+    /// The rule of the group that applies to the user agent matched the url.
+    Rule,
+    /// No rule of the groups that apply to the user agent matched the url, so the url is allowed.
+    NoMatchingRule,
+    /// The url belongs to another origin than the robots.txt file, so the url is disallowed.
+    OriginMismatch,
+    /// The robots.txt file itself is always allowed in the `EvaluationMode::Rfc9309` mode.
+    RobotsTxtPath,
+    /// The robots.txt file was not fetched, so its status decided:
+    /// everything is disallowed when access was denied or during the outage of the server,
+    /// and everything is allowed when the file is unavailable.
+    FetchStatus(FetchStatus),
+    /// The service does not explain its decisions, only the result of `RobotsTxtService::can_fetch` is known.
+    Unexplained,
+}
+
+/// Explanation of the result of `RobotsTxtService::can_fetch` that is returned by `RobotsTxtService::explain`.
+#[derive(Debug, Clone)]
+pub struct Decision {
+    allowed: bool,
+    reason: DecisionReason,
+    user_agents: Vec<String>,
+    default_group: bool,
+    group_spans: Vec<SourceSpan>,
+    rule: Option<Rule>,
+}
+
+impl Decision {
+    pub(crate) fn new(allowed: bool, reason: DecisionReason) -> Decision {
+        Decision {
+            allowed,
+            reason,
+            user_agents: Vec::new(),
+            default_group: false,
+            group_spans: Vec::new(),
+            rule: None,
+        }
+    }
+
+    /// Returns the decision made with the group that applies to the user agent.
+    pub(crate) fn from_group(allowed: bool, reason: DecisionReason, group: &Group) -> Decision {
+        Decision {
+            allowed,
+            reason,
+            user_agents: group.get_user_agents().to_vec(),
+            default_group: group.is_default(),
+            group_spans: group.get_spans().to_vec(),
+            rule: None,
+        }
+    }
+
+    /// Returns the decision made by the rule of the group.
+    pub(crate) fn from_rule(group: &Group, rule: &Rule) -> Decision {
+        Decision {
+            rule: Some(rule.clone()),
+            ..Decision::from_group(rule.get_allowance(), DecisionReason::Rule, group)
+        }
+    }
+
+    /// Checks that the url can be fetched.
+    pub fn is_allowed(&self) -> bool {
+        self.allowed
+    }
+
+    /// Returns what decided whether the url can be fetched.
+    pub fn get_reason(&self) -> DecisionReason {
+        self.reason
+    }
+
+    /// Returns the user agents of the group that applied to the user agent, in lower case.
+    /// The list is empty when no group applied.
+    pub fn get_user_agents(&self) -> &[String] {
+        self.user_agents.as_slice()
+    }
+
+    /// Checks that the default `*` group applied to the user agent.
+    pub fn is_default_group(&self) -> bool {
+        self.default_group
+    }
+
+    /// Returns the locations of the group that applied to the user agent in the robots.txt file.
+    pub fn get_group_spans(&self) -> &[SourceSpan] {
+        self.group_spans.as_slice()
+    }
+
+    /// Returns the rule that decided.
+    pub fn get_rule(&self) -> Option<&Rule> {
+        self.rule.as_ref()
+    }
+
+    /// Returns the number of the line of the robots.txt file where the deciding rule was defined.
+    pub fn get_line_no(&self) -> Option<usize> {
+        self.rule.as_ref().and_then(|rule| rule.get_line_no())
+    }
+}
@@ -9,6 +9,43 @@ pub struct Error {
 pub enum ErrorKind {
     Url(url::ParseError),
     Http(reqwest::Error),
+    Io(std::io::Error),
+    /// The url has no path that can be checked against the rules of the robots.txt file,
+    /// like the `mailto:` and `data:` urls.
+    UrlWithoutPath(url::Url),
+}
+
+/// Class of the error that happened when the robots.txt file was requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorClass {
+    /// The url of the robots.txt file or the checked url is invalid.
+    InvalidUrl,
+    /// The request timed out.
+    Timeout,
+    /// It is impossible to connect to the server or the connection was broken.
+    Connection,
+    /// Other errors.
+    Other,
+}
+
+impl Error {
+    /// Returns the class of this error.
+    pub fn get_class(&self) -> ErrorClass {
+        match self.kind {
+            ErrorKind::Url(..) => ErrorClass::InvalidUrl,
+            ErrorKind::Http(ref err) if err.is_timeout() => ErrorClass::Timeout,
+            ErrorKind::Http(ref err) if err.is_connect() || err.is_request() || err.is_body() => ErrorClass::Connection,
+            ErrorKind::Http(..) => ErrorClass::Other,
+            ErrorKind::Io(ref err) if err.kind() == std::io::ErrorKind::TimedOut => ErrorClass::Timeout,
+            ErrorKind::Io(..) => ErrorClass::Connection,
+            ErrorKind::UrlWithoutPath(..) => ErrorClass::InvalidUrl,
+        }
+    }
+
+    /// Checks that the server may become reachable again, so that the request can be retried.
+    pub fn is_transient(&self) -> bool {
+        matches!(self.get_class(), ErrorClass::Timeout | ErrorClass::Connection)
+    }
 }
 
 impl fmt::Display for Error {
@@ -16,6 +53,8 @@ impl fmt::Display for Error {
         match self.kind {
             ErrorKind::Url(ref err) => err.fmt(f),
             ErrorKind::Http(ref err) => err.fmt(f),
+            ErrorKind::Io(ref err) => err.fmt(f),
+            ErrorKind::UrlWithoutPath(ref url) => write!(f, "url has no path to check against robots.txt: {}", url),
         }
     }
 }
@@ -0,0 +1,28 @@
+use crate::model::user_agent_matcher::{ProductTokenMatcher, SubstringMatcher, UserAgentMatcher};
+
+/// Defines how the rules of the robots.txt file are evaluated by `robotparser::service::RobotsTxtService`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EvaluationMode {
+    /// Behaviour of the original norobots draft: the first group that applies to the user agent and has an answer
+    /// is used, falling back to the `*` group. Rules are tried from the longest to the shortest path pattern
+    /// and the first rule that applies wins.
+    #[default]
+    Legacy,
+    /// Behaviour described in RFC 9309 <https://www.rfc-editor.org/rfc/rfc9309>: only the most specific group
+    /// that matches the user agent is used (the `*` group only when no other group matches), the longest matching
+    /// rule wins and `Allow` wins when an `Allow` and a `Disallow` rule have the same length.
+    /// The `/robots.txt` path is always allowed.
+    Rfc9309,
+}
+
+impl EvaluationMode {
+    /// Returns the strategy used to find the groups that apply to a user agent when no strategy is set:
+    /// the `SubstringMatcher` in the legacy mode, and the `ProductTokenMatcher` in the RFC 9309 mode,
+    /// which compares the product tokens exactly and case-insensitively as RFC 9309 requires.
+    pub fn get_default_user_agent_matcher(self) -> &'static dyn UserAgentMatcher {
+        match self {
+            EvaluationMode::Legacy => &SubstringMatcher,
+            EvaluationMode::Rfc9309 => &ProductTokenMatcher,
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use crate::model::get_content_hash;
+use std::time::{Duration, SystemTime};
+use url::Url;
+
+/// Information about the HTTP-request that downloaded the robots.txt file.
+///
+/// # Example
+/// ```rust
+/// use robotparser::model::FetchMetadata;
+///
+/// let metadata = FetchMetadata::new(200).with_header("Cache-Control", "max-age=3600");
+/// assert_eq!(metadata.get_status_code(), Some(200));
+/// assert_eq!(metadata.get_header("cache-control"), Some("max-age=3600"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct FetchMetadata {
+    status_code: Option<u16>,
+    headers: Vec<(String, String)>,
+    fetched_at: SystemTime,
+    redirects: Vec<Url>,
+    final_url: Option<Url>,
+    latency: Option<Duration>,
+    body_size: Option<usize>,
+    content_hash: Option<u64>,
+}
+
+impl FetchMetadata {
+    /// Creates the information about the response with the HTTP status code, fetched now.
+    pub fn new(status_code: u16) -> FetchMetadata {
+        FetchMetadata {
+            status_code: Some(status_code),
+            headers: Vec::new(),
+            fetched_at: SystemTime::now(),
+            redirects: Vec::new(),
+            final_url: None,
+            latency: None,
+            body_size: None,
+            content_hash: None,
+        }
+    }
+
+    /// Creates the information about the request that did not get a response because of a network error.
+    pub fn without_response() -> FetchMetadata {
+        FetchMetadata {
+            status_code: None,
+            headers: Vec::new(),
+            fetched_at: SystemTime::now(),
+            redirects: Vec::new(),
+            final_url: None,
+            latency: None,
+            body_size: None,
+            content_hash: None,
+        }
+    }
+
+    /// Adds the response header.
+    pub fn with_header(mut self, name: &str, value: &str) -> FetchMetadata {
+        self.headers.push((name.to_lowercase(), value.into()));
+        self
+    }
+
+    /// Sets the system time when the response was received.
+    pub fn with_fetched_at(mut self, fetched_at: SystemTime) -> FetchMetadata {
+        self.fetched_at = fetched_at;
+        self
+    }
+
+    /// Sets the urls that responded with redirects, in the order the redirects were followed.
+    pub fn with_redirects(mut self, redirects: Vec<Url>) -> FetchMetadata {
+        self.redirects = redirects;
+        self
+    }
+
+    /// Sets the url of the response after all redirects.
+    pub fn with_final_url(mut self, final_url: Url) -> FetchMetadata {
+        self.final_url = Some(final_url);
+        self
+    }
+
+    /// Sets the time from sending the request to receiving the whole response body.
+    pub fn with_latency(mut self, latency: Duration) -> FetchMetadata {
+        self.latency = Some(latency);
+        self
+    }
+
+    /// Sets the size and the hash of the response body.
+    pub fn with_body(mut self, body: &str) -> FetchMetadata {
+        self.body_size = Some(body.len());
+        self.content_hash = Some(get_content_hash(body.as_bytes()));
+        self
+    }
+
+    /// Sets the size and the hash of the response body that is no longer available.
+    #[cfg(feature = "file-store")]
+    pub(crate) fn with_body_digest(mut self, body_size: usize, content_hash: u64) -> FetchMetadata {
+        self.body_size = Some(body_size);
+        self.content_hash = Some(content_hash);
+        self
+    }
+
+    /// Returns the information about the stored response updated with the `304 Not Modified` response.
+    /// The headers of the newer response replace the stored headers with the same name.
+    pub(crate) fn updated_with(&self, newer: FetchMetadata) -> FetchMetadata {
+        let mut headers: Vec<(String, String)> = self
+            .headers
+            .iter()
+            .filter(|(name, _)| newer.get_header(name).is_none())
+            .cloned()
+            .collect();
+        headers.extend(newer.headers);
+        FetchMetadata {
+            status_code: self.status_code,
+            headers,
+            fetched_at: newer.fetched_at,
+            redirects: newer.redirects,
+            final_url: newer.final_url,
+            latency: newer.latency,
+            body_size: self.body_size,
+            content_hash: self.content_hash,
+        }
+    }
+
+    /// Returns the HTTP status code of the response, or `None` if there was no response.
+    pub fn get_status_code(&self) -> Option<u16> {
+        self.status_code
+    }
+
+    /// Returns the value of the first response header with the name. The name is case-insensitive.
+    pub fn get_header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(item, _)| item.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the response headers. Names of the headers are lowercased.
+    pub fn get_headers(&self) -> &[(String, String)] {
+        self.headers.as_slice()
+    }
+
+    /// Returns the system time when the response was received.
+    pub fn get_fetched_at(&self) -> &SystemTime {
+        &self.fetched_at
+    }
+
+    /// Returns the urls that responded with redirects, in the order the redirects were followed.
+    /// If the http client follows redirects itself, only the url of the first redirect is known.
+    pub fn get_redirects(&self) -> &[Url] {
+        self.redirects.as_slice()
+    }
+
+    /// Returns the url of the response after all redirects.
+    pub fn get_final_url(&self) -> Option<&Url> {
+        self.final_url.as_ref()
+    }
+
+    /// Returns the time from sending the request to receiving the whole response body.
+    pub fn get_latency(&self) -> Option<Duration> {
+        self.latency
+    }
+
+    /// Returns the size of the response body in bytes.
+    pub fn get_body_size(&self) -> Option<usize> {
+        self.body_size
+    }
+
+    /// Returns the 64-bit FNV-1a hash of the response body.
+    /// The hash detects changes of the robots.txt file, but it is not cryptographically secure.
+    pub fn get_content_hash(&self) -> Option<u64> {
+        self.content_hash
+    }
+}
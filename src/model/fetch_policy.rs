@@ -0,0 +1,282 @@
+/// What the robots.txt model means for crawling when the robots.txt file was requested over the network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchOutcome {
+    /// The body of the response is parsed as the robots.txt file.
+    Parse,
+    /// Crawling of the whole origin is allowed.
+    AllowAll,
+    /// Crawling of the whole origin is disallowed.
+    DisallowAll,
+    /// The server is unreachable. Crawling of the whole origin is disallowed until the unreachable timeout
+    /// of the `FetchPolicy` elapses, then the cached copy of the robots.txt file is used if there is one,
+    /// otherwise crawling is allowed. See RFC 9309, section 2.3.1.4.
+    Unreachable,
+}
+
+use crate::model::redirect_policy::RedirectPolicy;
+use crate::model::retry_policy::RetryPolicy;
+use crate::parser::ParserOptions;
+use std::time::Duration;
+
+/// How long the server may stay unreachable before the cached copy or the full allow is used.
+const DEFAULT_UNREACHABLE_TIMEOUT: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// RFC 9309 requires crawlers to parse at least 500 kibibytes of the robots.txt file.
+const DEFAULT_MAX_BODY_SIZE: usize = 500 * 1024;
+
+/// Class of the HTTP status code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusClass {
+    /// `1xx` status codes.
+    Informational,
+    /// `2xx` status codes.
+    Success,
+    /// `3xx` status codes.
+    Redirection,
+    /// `4xx` status codes.
+    ClientError,
+    /// `5xx` status codes.
+    ServerError,
+    /// Status codes that do not belong to any class defined by the HTTP specification.
+    Unknown,
+}
+
+impl StatusClass {
+    /// Returns the class of the HTTP status code.
+    pub fn from_status_code(status_code: u16) -> StatusClass {
+        match status_code {
+            100..=199 => StatusClass::Informational,
+            200..=299 => StatusClass::Success,
+            300..=399 => StatusClass::Redirection,
+            400..=499 => StatusClass::ClientError,
+            500..=599 => StatusClass::ServerError,
+            _ => StatusClass::Unknown,
+        }
+    }
+}
+
+/// Maps the HTTP status code of the robots.txt response to the outcome for crawling.
+/// Outcomes of specific status codes take precedence over outcomes of status classes.
+///
+/// # Example
+/// ```rust
+/// use robotparser::model::{FetchOutcome, FetchPolicy};
+///
+/// let policy = FetchPolicy::rfc9309();
+/// assert_eq!(policy.get_outcome(200), FetchOutcome::Parse);
+/// assert_eq!(policy.get_outcome(404), FetchOutcome::AllowAll);
+/// assert_eq!(policy.get_outcome(503), FetchOutcome::Unreachable);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FetchPolicy {
+    status_codes: Vec<(u16, FetchOutcome)>,
+    informational: FetchOutcome,
+    success: FetchOutcome,
+    redirection: FetchOutcome,
+    client_error: FetchOutcome,
+    server_error: FetchOutcome,
+    unknown: FetchOutcome,
+    network_error: Option<FetchOutcome>,
+    unreachable_timeout: Duration,
+    retry_policy: RetryPolicy,
+    redirect_policy: RedirectPolicy,
+    max_body_size: usize,
+    parser_options: ParserOptions,
+}
+
+impl FetchPolicy {
+    /// Creates the policy described in RFC 9309: `2xx` responses are parsed, `4xx` responses
+    /// and unresolved redirects allow crawling, `5xx` responses, other responses and network errors
+    /// mean that the server is unreachable. Failed requests are retried twice,
+    /// and up to five consecutive redirects are followed.
+    pub fn rfc9309() -> FetchPolicy {
+        FetchPolicy {
+            status_codes: Vec::new(),
+            informational: FetchOutcome::Unreachable,
+            success: FetchOutcome::Parse,
+            redirection: FetchOutcome::AllowAll,
+            client_error: FetchOutcome::AllowAll,
+            server_error: FetchOutcome::Unreachable,
+            unknown: FetchOutcome::Unreachable,
+            network_error: Some(FetchOutcome::Unreachable),
+            unreachable_timeout: DEFAULT_UNREACHABLE_TIMEOUT,
+            retry_policy: RetryPolicy::new(2),
+            redirect_policy: RedirectPolicy::rfc9309(),
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            parser_options: ParserOptions::default(),
+        }
+    }
+
+    /// Creates the policy of Google crawlers. It is the same as `FetchPolicy::rfc9309`,
+    /// except that the `429 Too Many Requests` status code means that the server is unreachable
+    /// like `5xx` status codes.
+    /// <https://developers.google.com/search/docs/crawling-indexing/robots/robots_txt#http-status-codes>
+    pub fn google() -> FetchPolicy {
+        FetchPolicy::rfc9309().with_status_code(429, FetchOutcome::Unreachable)
+    }
+
+    /// Creates the policy of the previous versions of this crate:
+    /// `200 OK` responses are parsed, `401 Unauthorized` and `403 Forbidden` responses disallow crawling,
+    /// all other responses allow crawling. Failed requests are not retried and network errors are returned to the caller.
+    /// Redirects are not followed by the fetchers of this crate, the redirects followed by the http client are accepted.
+    pub fn legacy() -> FetchPolicy {
+        FetchPolicy {
+            status_codes: vec![
+                (200, FetchOutcome::Parse),
+                (401, FetchOutcome::DisallowAll),
+                (403, FetchOutcome::DisallowAll),
+            ],
+            informational: FetchOutcome::AllowAll,
+            success: FetchOutcome::AllowAll,
+            redirection: FetchOutcome::AllowAll,
+            client_error: FetchOutcome::AllowAll,
+            server_error: FetchOutcome::AllowAll,
+            unknown: FetchOutcome::AllowAll,
+            network_error: None,
+            unreachable_timeout: DEFAULT_UNREACHABLE_TIMEOUT,
+            retry_policy: RetryPolicy::none(),
+            redirect_policy: RedirectPolicy::none(),
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            parser_options: ParserOptions::default(),
+        }
+    }
+
+    /// Sets the outcome of the specific HTTP status code.
+    pub fn with_status_code(mut self, status_code: u16, outcome: FetchOutcome) -> FetchPolicy {
+        self.status_codes.retain(|(item, _)| *item != status_code);
+        self.status_codes.push((status_code, outcome));
+        self
+    }
+
+    /// Sets the outcome of all HTTP status codes of the class.
+    /// Outcomes of specific status codes of this class are kept.
+    pub fn with_status_class(mut self, status_class: StatusClass, outcome: FetchOutcome) -> FetchPolicy {
+        match status_class {
+            StatusClass::Informational => self.informational = outcome,
+            StatusClass::Success => self.success = outcome,
+            StatusClass::Redirection => self.redirection = outcome,
+            StatusClass::ClientError => self.client_error = outcome,
+            StatusClass::ServerError => self.server_error = outcome,
+            StatusClass::Unknown => self.unknown = outcome,
+        }
+        self
+    }
+
+    /// Sets the outcome of timeouts and connection failures that remain after all retries.
+    /// If the outcome is `None`, the network error is returned to the caller.
+    pub fn with_network_error(mut self, outcome: Option<FetchOutcome>) -> FetchPolicy {
+        self.network_error = outcome;
+        self
+    }
+
+    /// Sets how long the server may stay unreachable before the cached copy of the robots.txt file
+    /// or the full allow is used. The default timeout is 30 days.
+    pub fn with_unreachable_timeout(mut self, unreachable_timeout: Duration) -> FetchPolicy {
+        self.unreachable_timeout = unreachable_timeout;
+        self
+    }
+
+    /// Sets how failed requests are retried.
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> FetchPolicy {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets how redirects are followed.
+    ///
+    /// The maximum number of redirects is enforced only when the http client leaves the redirects to the fetcher,
+    /// e.g. the reqwest client is created with `reqwest::redirect::Policy::none()`.
+    /// The redirects that the client follows itself are counted as a single redirect, see `RedirectPolicy`.
+    pub fn with_redirect_policy(mut self, redirect_policy: RedirectPolicy) -> FetchPolicy {
+        self.redirect_policy = redirect_policy;
+        self
+    }
+
+    /// Sets the maximum size of the robots.txt file in bytes. The default size is 500 KiB.
+    /// The fetchers stop reading the body after this size, and the text that exceeds it is cut off
+    /// at the end of the last complete line, so that only the rules before the cut-off apply.
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> FetchPolicy {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Sets the options of the parser of the robots.txt file.
+    pub fn with_parser_options(mut self, parser_options: ParserOptions) -> FetchPolicy {
+        self.parser_options = parser_options;
+        self
+    }
+
+    /// Returns the outcome of timeouts and connection failures.
+    pub fn get_network_error_outcome(&self) -> Option<FetchOutcome> {
+        self.network_error
+    }
+
+    /// Returns how long the server may stay unreachable before the cached copy or the full allow is used.
+    pub fn get_unreachable_timeout(&self) -> Duration {
+        self.unreachable_timeout
+    }
+
+    /// Returns how failed requests are retried.
+    pub fn get_retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    /// Returns how redirects are followed.
+    pub fn get_redirect_policy(&self) -> &RedirectPolicy {
+        &self.redirect_policy
+    }
+
+    /// Returns the maximum size of the robots.txt file in bytes.
+    pub fn get_max_body_size(&self) -> usize {
+        self.max_body_size
+    }
+
+    /// Returns the options of the parser of the robots.txt file.
+    pub fn get_parser_options(&self) -> &ParserOptions {
+        &self.parser_options
+    }
+
+    /// Checks that the request must be retried after the response with the HTTP status code.
+    /// The responses that mean that the server is unreachable are retried. The `429 Too Many Requests`
+    /// status code is retried too, unless the policy sets the outcome of this status code explicitly.
+    pub fn is_retryable_status_code(&self, status_code: u16) -> bool {
+        match self.get_status_code_outcome(status_code) {
+            Some(outcome) => outcome == FetchOutcome::Unreachable,
+            None if status_code == 429 => true,
+            None => {
+                StatusClass::from_status_code(status_code) == StatusClass::ServerError
+                    && self.server_error == FetchOutcome::Unreachable
+            }
+        }
+    }
+
+    /// Returns the outcome that is set for the specific HTTP status code.
+    fn get_status_code_outcome(&self, status_code: u16) -> Option<FetchOutcome> {
+        self.status_codes
+            .iter()
+            .find(|(item, _)| *item == status_code)
+            .map(|(_, outcome)| *outcome)
+    }
+
+    /// Returns the outcome of the HTTP status code.
+    pub fn get_outcome(&self, status_code: u16) -> FetchOutcome {
+        if let Some(outcome) = self.get_status_code_outcome(status_code) {
+            return outcome;
+        }
+        match StatusClass::from_status_code(status_code) {
+            StatusClass::Informational => self.informational,
+            StatusClass::Success => self.success,
+            StatusClass::Redirection => self.redirection,
+            StatusClass::ClientError => self.client_error,
+            StatusClass::ServerError => self.server_error,
+            StatusClass::Unknown => self.unknown,
+        }
+    }
+}
+
+/// The default policy is `FetchPolicy::legacy` to keep the behaviour of the previous versions of this crate.
+impl Default for FetchPolicy {
+    fn default() -> FetchPolicy {
+        FetchPolicy::legacy()
+    }
+}
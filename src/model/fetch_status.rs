@@ -0,0 +1,12 @@
+/// State of the robots.txt file that was downloaded over the network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchStatus {
+    /// The robots.txt file was downloaded and parsed.
+    Fetched,
+    /// Access to the robots.txt file was denied, so crawling is fully disallowed.
+    Denied,
+    /// The robots.txt file is unavailable, so crawling is fully allowed.
+    Failed,
+    /// The server was unreachable when the robots.txt file was requested.
+    Unreachable,
+}
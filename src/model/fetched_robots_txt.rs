@@ -1,11 +1,30 @@
+use crate::model::clock::Clock;
+use crate::model::evaluation_mode::EvaluationMode;
+use crate::model::fetch_metadata::FetchMetadata;
+use crate::model::fetch_status::FetchStatus;
+use crate::model::http_date::parse_http_date;
 use crate::model::robots_txt::RobotsTxt;
-use std::time::SystemTime;
+use crate::model::user_agent_matcher::UserAgentMatcher;
+use crate::user_agent::AgentFamilies;
+use std::time::{Duration, SystemTime};
+
+/// RFC 9309 says that crawlers should not use the cached robots.txt file for more than 24 hours.
+const MAX_CACHE_LIFETIME: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How long the result of the unreachable server is used when the response has neither cache headers
+/// nor the `Retry-After` header, so that the robots.txt file is retried soon after the outage.
+const UNREACHABLE_CACHE_LIFETIME: Duration = Duration::from_secs(5 * 60);
 
 #[derive(Debug, Clone)]
 pub(crate) enum FetchedRobotsTxtContainer {
     FetchDenied,
     FetchFailed,
     Fetched(RobotsTxt),
+    Unreachable {
+        since: SystemTime,
+        timeout: Duration,
+        cached: Option<Box<RobotsTxt>>,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -14,23 +33,307 @@ pub(crate) enum FetchedRobotsTxtContainer {
 /// To work with this model you should use the trait `robotparser::service::RobotsTxtService`.
 /// To create this structure you should use the `robotparser::parser::parse_fetched_robots_txt`.
 pub struct FetchedRobotsTxt {
-    fetched_at: SystemTime,
+    metadata: FetchMetadata,
     container: FetchedRobotsTxtContainer,
+    body: Option<String>,
+    refresh_failures: u32,
+    last_refresh_failure: Option<SystemTime>,
 }
 
 impl FetchedRobotsTxt {
-    pub(crate) fn new(container: FetchedRobotsTxtContainer) -> FetchedRobotsTxt {
+    pub(crate) fn new(metadata: FetchMetadata, container: FetchedRobotsTxtContainer) -> FetchedRobotsTxt {
         FetchedRobotsTxt {
-            fetched_at: SystemTime::now(),
+            metadata,
             container,
+            body: None,
+            refresh_failures: 0,
+            last_refresh_failure: None,
+        }
+    }
+
+    pub(crate) fn unreachable(metadata: FetchMetadata, timeout: Duration) -> FetchedRobotsTxt {
+        let since = *metadata.get_fetched_at();
+        FetchedRobotsTxt {
+            metadata,
+            container: FetchedRobotsTxtContainer::Unreachable {
+                since,
+                timeout,
+                cached: None,
+            },
+            body: None,
+            refresh_failures: 0,
+            last_refresh_failure: None,
         }
     }
+
+    /// Keeps the text of the robots.txt file that the model was parsed from.
+    pub(crate) fn with_body(mut self, body: &str) -> FetchedRobotsTxt {
+        self.body = Some(body.into());
+        self
+    }
+
     pub(crate) fn get_container(&self) -> &FetchedRobotsTxtContainer {
         &self.container
     }
 
+    /// Returns the robots.txt model whose rules currently apply.
+    /// While the server is unreachable it is the cached copy, but only after the unreachable timeout elapsed.
+    pub(crate) fn get_effective_robots_txt(&self, clock: &dyn Clock) -> Option<&RobotsTxt> {
+        match self.container {
+            FetchedRobotsTxtContainer::Fetched(ref robots_txt) => Some(robots_txt),
+            FetchedRobotsTxtContainer::Unreachable { ref cached, .. } if self.is_unreachable_timeout_elapsed(clock) => {
+                cached.as_deref()
+            }
+            _ => None,
+        }
+    }
+
+    /// Carries the state of an outage over from the previous result for the same origin.
+    ///
+    /// If this result says that the server is unreachable, the outage is considered to have started
+    /// when the previous result became unreachable, and the previously downloaded robots.txt file
+    /// is kept as the cached copy. Other results are returned unchanged.
+    pub fn with_previous(mut self, previous: &FetchedRobotsTxt) -> FetchedRobotsTxt {
+        if let FetchedRobotsTxtContainer::Unreachable {
+            ref mut since,
+            ref mut cached,
+            ..
+        } = self.container
+        {
+            match previous.container {
+                FetchedRobotsTxtContainer::Fetched(ref robots_txt) => {
+                    *cached = Some(Box::new(robots_txt.clone()));
+                }
+                FetchedRobotsTxtContainer::Unreachable {
+                    since: previous_since,
+                    cached: ref previous_cached,
+                    ..
+                } => {
+                    *since = previous_since.min(*since);
+                    *cached = previous_cached.clone();
+                }
+                FetchedRobotsTxtContainer::FetchDenied | FetchedRobotsTxtContainer::FetchFailed => {}
+            }
+        }
+        self
+    }
+
+    /// Sets the time when the server became unreachable, if this result says that the server is unreachable.
+    #[cfg(feature = "file-store")]
+    pub(crate) fn with_unreachable_since(mut self, unreachable_since: SystemTime) -> FetchedRobotsTxt {
+        if let FetchedRobotsTxtContainer::Unreachable { ref mut since, .. } = self.container {
+            *since = unreachable_since;
+        }
+        self
+    }
+
+    /// Returns the copy of this result that was confirmed by the `304 Not Modified` response.
+    /// The parsed model is kept, and the lifetime is extended according to the headers of the response.
+    pub(crate) fn revalidated(&self, metadata: FetchMetadata) -> FetchedRobotsTxt {
+        let mut robots_txt = FetchedRobotsTxt::new(self.metadata.updated_with(metadata), self.container.clone());
+        robots_txt.body = self.body.clone();
+        robots_txt
+    }
+
+    /// Returns the copy of this result that records one more failed attempt to refresh it.
+    pub(crate) fn with_refresh_failure(&self, failed_at: SystemTime) -> FetchedRobotsTxt {
+        let mut robots_txt = self.clone();
+        robots_txt.refresh_failures += 1;
+        robots_txt.last_refresh_failure = Some(failed_at);
+        robots_txt
+    }
+
+    /// Returns the number of consecutive attempts to refresh this result that failed.
+    /// Failed refreshes keep the previous result, see `RobotsResolver::with_stale_while_revalidate`.
+    pub fn get_refresh_failures(&self) -> u32 {
+        self.refresh_failures
+    }
+
+    /// Returns the system time of the last failed attempt to refresh this result.
+    pub fn get_last_refresh_failure(&self) -> Option<SystemTime> {
+        self.last_refresh_failure
+    }
+
+    /// Checks that the server was unreachable when the robots.txt file was requested.
+    pub fn is_unreachable(&self) -> bool {
+        matches!(self.container, FetchedRobotsTxtContainer::Unreachable { .. })
+    }
+
+    /// Returns the system time when the server became unreachable, if it is unreachable.
+    pub fn get_unreachable_since(&self) -> Option<SystemTime> {
+        match self.container {
+            FetchedRobotsTxtContainer::Unreachable { since, .. } => Some(since),
+            _ => None,
+        }
+    }
+
+    /// Checks that the server has been unreachable for longer than the unreachable timeout,
+    /// so that the cached copy or the full allow is used instead of the full disallow.
+    pub fn is_unreachable_timeout_elapsed(&self, clock: &dyn Clock) -> bool {
+        match self.container {
+            FetchedRobotsTxtContainer::Unreachable { since, timeout, .. } => clock
+                .now()
+                .duration_since(since)
+                .map(|elapsed| elapsed >= timeout)
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+
+    /// Sets the mode used to evaluate the rules of the downloaded robots.txt file.
+    /// The default mode is `EvaluationMode::Legacy`.
+    pub fn set_evaluation_mode(&mut self, evaluation_mode: EvaluationMode) {
+        if let Some(robots_txt) = self.get_mut_robots_txt() {
+            robots_txt.set_evaluation_mode(evaluation_mode);
+        }
+    }
+
+    /// Sets the strategy used to find the groups of the downloaded robots.txt file that apply to a user agent.
+    /// The default strategy depends on the evaluation mode, see `EvaluationMode::get_default_user_agent_matcher`.
+    pub fn set_user_agent_matcher(&mut self, user_agent_matcher: impl UserAgentMatcher + 'static) {
+        if let Some(robots_txt) = self.get_mut_robots_txt() {
+            robots_txt.set_user_agent_matcher(user_agent_matcher);
+        }
+    }
+
+    /// Sets the fallback chains of crawler families used to find the groups of the downloaded robots.txt file.
+    pub fn set_agent_families(&mut self, agent_families: AgentFamilies) {
+        if let Some(robots_txt) = self.get_mut_robots_txt() {
+            robots_txt.set_agent_families(agent_families);
+        }
+    }
+
+    fn get_mut_robots_txt(&mut self) -> Option<&mut RobotsTxt> {
+        match self.container {
+            FetchedRobotsTxtContainer::Fetched(ref mut robots_txt) => Some(robots_txt),
+            FetchedRobotsTxtContainer::Unreachable {
+                cached: Some(ref mut robots_txt),
+                ..
+            } => Some(robots_txt),
+            _ => None,
+        }
+    }
+
     /// Returns the system time when the robots.txt file was downloaded over the network.
     pub fn get_fetched_at(&self) -> &SystemTime {
-        &self.fetched_at
+        self.metadata.get_fetched_at()
+    }
+
+    /// Returns the state of the robots.txt file.
+    pub fn get_status(&self) -> FetchStatus {
+        match self.container {
+            FetchedRobotsTxtContainer::FetchDenied => FetchStatus::Denied,
+            FetchedRobotsTxtContainer::FetchFailed => FetchStatus::Failed,
+            FetchedRobotsTxtContainer::Fetched(..) => FetchStatus::Fetched,
+            FetchedRobotsTxtContainer::Unreachable { .. } => FetchStatus::Unreachable,
+        }
+    }
+
+    /// Returns the HTTP status code of the response, or `None` if there was no response.
+    pub fn get_status_code(&self) -> Option<u16> {
+        self.metadata.get_status_code()
+    }
+
+    /// Returns the information about the HTTP-request that downloaded the robots.txt file.
+    pub fn get_metadata(&self) -> &FetchMetadata {
+        &self.metadata
+    }
+
+    /// Returns the text of the robots.txt file if it was parsed.
+    /// The text is kept so that the robots.txt file can be stored and parsed again later.
+    pub fn get_body(&self) -> Option<&str> {
+        self.body.as_deref()
+    }
+
+    /// Returns the value of the `ETag` header of the robots.txt file.
+    /// It is sent in the `If-None-Match` header when the robots.txt file is revalidated.
+    pub fn get_etag(&self) -> Option<&str> {
+        self.metadata.get_header("etag")
+    }
+
+    /// Returns the value of the `Last-Modified` header of the robots.txt file.
+    /// It is sent in the `If-Modified-Since` header when the robots.txt file is revalidated.
+    pub fn get_last_modified(&self) -> Option<&str> {
+        self.metadata.get_header("last-modified")
+    }
+
+    /// Returns how long this result may be used before the robots.txt file must be fetched again.
+    ///
+    /// The lifetime is taken from the `max-age` directive of the `Cache-Control` header,
+    /// then from the `Expires` header. The `Age` header is subtracted from the lifetime.
+    /// `no-cache` and `no-store` directives make the lifetime zero.
+    /// Without these headers, and as the upper bound, the lifetime is 24 hours as RFC 9309 recommends.
+    /// When the server is unreachable and there are no cache headers, the lifetime is taken from
+    /// the `Retry-After` header or is 5 minutes, and it never exceeds the unreachable timeout.
+    pub fn get_cache_lifetime(&self) -> Duration {
+        let lifetime = match self.get_header_cache_lifetime() {
+            Some(lifetime) => lifetime,
+            None => match self.container {
+                FetchedRobotsTxtContainer::Unreachable { timeout, .. } => self
+                    .get_retry_after()
+                    .unwrap_or(UNREACHABLE_CACHE_LIFETIME)
+                    .min(timeout),
+                _ => MAX_CACHE_LIFETIME,
+            },
+        };
+        let age = self
+            .metadata
+            .get_header("age")
+            .and_then(|age| age.trim().parse::<u64>().ok())
+            .map(Duration::from_secs)
+            .unwrap_or_default();
+        lifetime.saturating_sub(age).min(MAX_CACHE_LIFETIME)
+    }
+
+    fn get_header_cache_lifetime(&self) -> Option<Duration> {
+        if let Some(cache_control) = self.metadata.get_header("cache-control") {
+            let mut max_age = None;
+            for directive in cache_control.split(',') {
+                let directive = directive.trim().to_lowercase();
+                if directive == "no-cache" || directive == "no-store" {
+                    return Some(Duration::from_secs(0));
+                }
+                if let Some(value) = directive.strip_prefix("max-age=") {
+                    max_age = value.trim_matches('"').parse::<u64>().ok().map(Duration::from_secs);
+                }
+            }
+            if max_age.is_some() {
+                return max_age;
+            }
+        }
+        let expires = self.metadata.get_header("expires")?;
+        // Invalid dates, e.g. `0`, mean that the response is already expired.
+        let expires = parse_http_date(expires).unwrap_or(SystemTime::UNIX_EPOCH);
+        Some(expires.duration_since(self.get_response_date()).unwrap_or_default())
+    }
+
+    /// Returns the delay of the `Retry-After` header, either in seconds or as the HTTP date.
+    fn get_retry_after(&self) -> Option<Duration> {
+        let value = self.metadata.get_header("retry-after")?.trim();
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+        let retry_at = parse_http_date(value)?;
+        Some(retry_at.duration_since(self.get_response_date()).unwrap_or_default())
+    }
+
+    /// Returns the value of the `Date` header, or the time when the response was received.
+    fn get_response_date(&self) -> SystemTime {
+        self.metadata
+            .get_header("date")
+            .and_then(parse_http_date)
+            .unwrap_or(*self.metadata.get_fetched_at())
+    }
+
+    /// Returns the system time when this result expires and the robots.txt file must be fetched again.
+    /// If the time is not representable, the result expires when it was fetched.
+    pub fn expires_at(&self) -> SystemTime {
+        let fetched_at = *self.metadata.get_fetched_at();
+        fetched_at.checked_add(self.get_cache_lifetime()).unwrap_or(fetched_at)
+    }
+
+    /// Checks that this result is expired at the current time of the clock.
+    pub fn is_expired(&self, clock: &dyn Clock) -> bool {
+        clock.now() >= self.expires_at()
     }
 }
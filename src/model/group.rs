@@ -1,5 +1,10 @@
+use crate::model::evaluation_mode::EvaluationMode;
+use crate::model::path::Path;
 use crate::model::request_rate::RequestRate;
 use crate::model::rule::Rule;
+use crate::model::rule_index::{RuleIndex, RuleMatch};
+use crate::model::source_span::SourceSpan;
+use crate::model::user_agent_matcher::UserAgentMatcher;
 use std::time::Duration;
 
 /// An group has one or more user-agents and zero or more rules
@@ -7,8 +12,12 @@ use std::time::Duration;
 pub struct Group {
     user_agents: Vec<String>,
     rules: Vec<Rule>,
+    index: RuleIndex,
     crawl_delay: Option<Duration>,
     req_rate: Option<RequestRate>,
+    spans: Vec<SourceSpan>,
+    crawl_delay_span: Option<SourceSpan>,
+    req_rate_span: Option<SourceSpan>,
 }
 
 impl Group {
@@ -16,20 +25,52 @@ impl Group {
         Group {
             user_agents: vec![],
             rules: vec![],
+            index: RuleIndex::new(&[]),
             crawl_delay: None,
             req_rate: None,
+            spans: vec![],
+            crawl_delay_span: None,
+            req_rate_span: None,
         }
     }
 
     /// check if this group applies to the specified agent
-    pub(crate) fn applies_to(&self, useragent: &str) -> bool {
-        let ua = useragent.split('/').next().unwrap_or("").to_lowercase();
-        for agent in self.user_agents.iter() {
-            if ua.contains(agent) {
-                return true;
-            }
+    pub(crate) fn applies_to(&self, user_agent: &str, matcher: &dyn UserAgentMatcher) -> bool {
+        self.user_agents.iter().any(|agent| matcher.matches(agent, user_agent))
+    }
+
+    /// Returns the length of the longest user agent of this group that applies to the specified agent.
+    /// The `*` user agent is not taken into account.
+    pub(crate) fn get_specific_match_len(&self, user_agent: &str, matcher: &dyn UserAgentMatcher) -> Option<usize> {
+        self.user_agents
+            .iter()
+            .filter(|agent| *agent != "*" && matcher.matches(agent, user_agent))
+            .map(|agent| agent.len())
+            .max()
+    }
+
+    /// Returns the user agents of this group in lower case.
+    pub fn get_user_agents(&self) -> &[String] {
+        self.user_agents.as_slice()
+    }
+
+    /// Returns the rules of this group in order of appearance.
+    pub fn get_rules(&self) -> &[Rule] {
+        self.rules.as_slice()
+    }
+
+    /// Returns the locations of this group in the robots.txt file.
+    /// The group that was merged from several groups with the same user agent has a location for each of them.
+    pub fn get_spans(&self) -> &[SourceSpan] {
+        self.spans.as_slice()
+    }
+
+    /// Extends the last location of this group up to the span, or starts it.
+    pub(crate) fn extend_span(&mut self, span: SourceSpan) {
+        match self.spans.last_mut() {
+            Some(last) => *last = last.to(span),
+            None => self.spans.push(span),
         }
-        false
     }
 
     pub(crate) fn push_useragent(&mut self, useragent: &str) {
@@ -40,36 +81,66 @@ impl Group {
         self.rules.push(rule);
     }
 
-    pub(crate) fn get_rules_sorted_by_path_len_desc(&self) -> Vec<&Rule> {
-        let mut rules: Vec<&Rule> = self.rules.iter().collect();
-        rules.sort_by(|a, b| {
-            let a = a.get_path_pattern().len();
-            let b = b.get_path_pattern().len();
-            b.cmp(&a)
-        });
-        rules
+    /// Compiles the index of the rules of this group. Must be called when all rules are added.
+    pub(crate) fn build_index(&mut self) {
+        self.index = RuleIndex::new(&self.rules);
+    }
+
+    /// Returns the rule of this group that takes precedence for the path in the evaluation mode.
+    pub(crate) fn find_rule(&self, path: &Path, evaluation_mode: EvaluationMode) -> Option<RuleMatch> {
+        self.index.find(&self.rules, path, evaluation_mode)
     }
 
-    pub(crate) fn contains_user_agent(&self, user_agent: &str) -> bool {
-        self.user_agents.iter().any(|item| *item == user_agent)
+    /// Adds the rules of other group to this group.
+    /// Crawl delay and request rate of this group take precedence over the values of other group.
+    pub(crate) fn merge(&mut self, other: &Group) {
+        self.rules.extend_from_slice(&other.rules);
+        self.spans.extend_from_slice(&other.spans);
+        if self.crawl_delay.is_none() {
+            self.crawl_delay = other.crawl_delay;
+            self.crawl_delay_span = other.crawl_delay_span;
+        }
+        if self.req_rate.is_none() {
+            self.req_rate = other.req_rate.clone();
+            self.req_rate_span = other.req_rate_span;
+        }
     }
 
-    pub(crate) fn set_crawl_delay(&mut self, delay: Duration) {
+    /// Drops the rules of this group after the first `max_rules` rules.
+    pub(crate) fn truncate_rules(&mut self, max_rules: usize) {
+        self.rules.truncate(max_rules);
+    }
+
+    pub(crate) fn set_crawl_delay(&mut self, delay: Duration, span: SourceSpan) {
         self.crawl_delay = Some(delay);
+        self.crawl_delay_span = Some(span);
     }
 
-    pub(crate) fn get_crawl_delay(&self) -> Option<Duration> {
+    /// Returns the crawl delay of this group.
+    pub fn get_crawl_delay(&self) -> Option<Duration> {
         self.crawl_delay
     }
 
-    pub(crate) fn set_req_rate(&mut self, req_rate: RequestRate) {
+    /// Returns the location of the `Crawl-delay` directive of this group.
+    pub fn get_crawl_delay_span(&self) -> Option<SourceSpan> {
+        self.crawl_delay_span
+    }
+
+    pub(crate) fn set_req_rate(&mut self, req_rate: RequestRate, span: SourceSpan) {
         self.req_rate = Some(req_rate);
+        self.req_rate_span = Some(span);
     }
 
-    pub(crate) fn get_req_rate(&self) -> Option<RequestRate> {
+    /// Returns the request rate of this group.
+    pub fn get_req_rate(&self) -> Option<RequestRate> {
         self.req_rate.clone()
     }
 
+    /// Returns the location of the `Request-rate` directive of this group.
+    pub fn get_req_rate_span(&self) -> Option<SourceSpan> {
+        self.req_rate_span
+    }
+
     pub(crate) fn is_default(&self) -> bool {
         for user_agent in self.user_agents.iter() {
             if user_agent == "*" {
@@ -0,0 +1,56 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The HTTP-date has a four-digit year, later dates are rejected.
+const MAX_YEAR: u64 = 9999;
+
+const MONTHS: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+
+/// Parses the HTTP-date value of headers like `Expires` or `Retry-After`.
+/// Supports all formats of RFC 9110: IMF-fixdate, obsolete RFC 850 and asctime formats.
+pub(crate) fn parse_http_date(value: &str) -> Option<SystemTime> {
+    let parts: Vec<&str> = value
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '-')
+        .filter(|part| !part.is_empty())
+        .collect();
+    let (day, month, year, time) = match parts.as_slice() {
+        // Sun, 06 Nov 1994 08:49:37 GMT
+        // Sunday, 06-Nov-94 08:49:37 GMT
+        [_, day, month, year, time, "GMT"] => (*day, *month, *year, *time),
+        // Sun Nov  6 08:49:37 1994
+        [_, month, day, time, year] => (*day, *month, *year, *time),
+        _ => return None,
+    };
+    let day: u64 = day.parse().ok()?;
+    let month = MONTHS.iter().position(|item| item.eq_ignore_ascii_case(month))? as u64 + 1;
+    let mut year: u64 = year.parse().ok()?;
+    if year < 100 {
+        // RFC 850 two-digit years.
+        year += if year < 70 { 2000 } else { 1900 };
+    }
+    let time: Vec<u64> = time.split(':').map(|part| part.parse().ok()).collect::<Option<_>>()?;
+    let (hours, minutes, seconds) = match time.as_slice() {
+        [hours, minutes, seconds] if *hours < 24 && *minutes < 60 && *seconds < 61 => (*hours, *minutes, *seconds),
+        _ => return None,
+    };
+    if !(1..=31).contains(&day) || !(1970..=MAX_YEAR).contains(&year) {
+        return None;
+    }
+    let days = days_from_unix_epoch(year, month, day)?;
+    let seconds = days
+        .checked_mul(86400)?
+        .checked_add(hours * 3600 + minutes * 60 + seconds)?;
+    UNIX_EPOCH.checked_add(Duration::from_secs(seconds))
+}
+
+/// Returns the number of days since 1970-01-01 for the date of the proleptic Gregorian calendar.
+fn days_from_unix_epoch(year: u64, month: u64, day: u64) -> Option<u64> {
+    let year = if month <= 2 { year.checked_sub(1)? } else { year };
+    let era = year / 400;
+    let year_of_era = year % 400;
+    let month = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era.checked_mul(146097)?.checked_add(day_of_era)?.checked_sub(719468)
+}
@@ -1,35 +1,56 @@
-use percent_encoding::percent_decode;
-use url::Url;
+use crate::model::errors::{Error, ErrorKind};
+use crate::model::percent_encoding_normalizer::normalize_percent_encoding;
+use std::borrow::Cow;
+use url::{Position, Url};
 
-#[derive(Debug)]
-pub struct Path(String);
+/// The part of the URL that is matched against the path patterns of the robots.txt file.
+///
+/// The path is taken from the canonical form of the URL (see `canonicalize_url`) and is followed
+/// by the query if the URL has one, the fragment is never matched. An empty query is kept as `?`,
+/// so that it matches the patterns like `/path?`. The percent-encoding is normalized as described in RFC 9309.
+/// The path borrows from the URL when its percent-encoding is already normalized.
+///
+/// # Example
+/// ```rust
+/// use robotparser::model::Path;
+/// use url::Url;
+///
+/// let url = Url::parse("https://example.com:443/a/./b/../%7euser/?#top").unwrap();
+/// assert_eq!(Path::from_url(&url).as_str(), "/a/~user/?");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<'a>(Cow<'a, str>);
 
-impl Path {
-    pub fn from_url(url: &Url) -> Path {
-        let path = get_url_without_origin(url);
-        let path = percent_decode(path.as_bytes()).decode_utf8_lossy();
+impl<'a> Path<'a> {
+    /// Returns the path of the URL that is matched against the rules.
+    pub fn from_url(url: &'a Url) -> Path<'a> {
+        let path = &url[Position::BeforePath..Position::AfterQuery];
         if path.is_empty() {
-            Path("/".into())
-        } else {
-            Path(path.into())
+            return Path(Cow::Borrowed("/"));
         }
+        Path(normalize_percent_encoding(path))
     }
+
+    /// Returns the path of the URL that is matched against the rules.
+    /// Returns the error for the URLs that have no hierarchical path, like `mailto:` and `data:` URLs.
+    pub fn try_from_url(url: &'a Url) -> Result<Path<'a>, Error> {
+        if url.cannot_be_a_base() {
+            return Err(Error {
+                kind: ErrorKind::UrlWithoutPath(url.clone()),
+            });
+        }
+        Ok(Path::from_url(url))
+    }
+
+    /// Returns the string that is matched against the rules.
     pub fn as_str(&self) -> &str {
         &self.0
     }
 }
 
-fn get_url_without_origin(url: &Url) -> &str {
-    let origin = url.origin();
-    let url = url.as_str();
-    let unicode_origin = origin.unicode_serialization();
-    let ascii_origin = origin.ascii_serialization();
-    if url.starts_with(&unicode_origin) && !unicode_origin.is_empty() {
-        return &url[unicode_origin.len()..];
-    }
-    if url.starts_with(&ascii_origin) && !ascii_origin.is_empty() {
-        return &url[ascii_origin.len()..];
-    }
-    // Must never be executed.
-    panic!("Unable to get path from url");
+/// Converts the URL to the canonical form that is used to check the rules of the robots.txt file.
+/// The fragment is removed. The URL parser already resolves the `.` and `..` segments of the path
+/// and removes the default port of the scheme. The empty query is kept as `?`, because the rules match it.
+pub fn canonicalize_url(url: &mut Url) {
+    url.set_fragment(None);
 }
@@ -1,10 +1,25 @@
 use crate::model::path::Path;
-use percent_encoding::percent_decode;
+use crate::model::percent_encoding_normalizer::normalize_percent_encoding;
 use std::convert::From;
 use std::mem::replace;
 
+/// Path pattern of the `Allow`, `Disallow` and `Clean-Param` directives.
+/// The `*` wildcard matches any sequence of characters, and `$` matches the end of the path.
 #[derive(Debug, Clone)]
-pub struct PathPattern(Vec<PathPatternToken>);
+pub struct PathPattern {
+    pattern: String,
+    tokens: Vec<PathPatternToken>,
+    segments: Option<Segments>,
+}
+
+/// The literal parts of the path pattern that are separated by `*` wildcards.
+/// The first part must match at the start of the path, the others in order after it.
+/// The anchored pattern must match up to the end of the path, other patterns match any path with the matching prefix.
+#[derive(Debug, Clone)]
+struct Segments {
+    parts: Vec<String>,
+    anchored: bool,
+}
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 enum PathPatternToken {
@@ -15,8 +30,7 @@ enum PathPatternToken {
 
 impl PathPatternToken {
     fn from_path_pattern(path: String) -> PathPatternToken {
-        let path = percent_decode(path.as_bytes()).decode_utf8_lossy();
-        PathPatternToken::Text(path.to_string())
+        PathPatternToken::Text(normalize_percent_encoding(&path).into_owned())
     }
 }
 
@@ -57,56 +71,116 @@ impl PathPattern {
             tokens.push(PathPatternToken::AnyString);
         }
         tokens.dedup();
-        PathPattern(tokens)
+        PathPattern::from_tokens(path.into(), tokens)
     }
 
     pub fn all() -> PathPattern {
-        PathPattern(vec![PathPatternToken::AnyString])
+        PathPattern::from_tokens("*".into(), vec![PathPatternToken::AnyString])
+    }
+
+    fn from_tokens(pattern: String, tokens: Vec<PathPatternToken>) -> PathPattern {
+        let segments = Segments::new(&tokens);
+        PathPattern {
+            pattern,
+            tokens,
+            segments,
+        }
+    }
+
+    /// Returns the pattern as it was written in the robots.txt file.
+    pub fn as_str(&self) -> &str {
+        &self.pattern
     }
 
+    /// Checks that the pattern matches the path.
+    /// The time of the check is linear in the length of the path and the pattern, since the literal parts
+    /// between the wildcards are searched from left to right, and the leftmost occurrence of each part
+    /// never prevents the following parts from matching.
     pub fn applies_to(&self, path: &Path) -> bool {
-        let mut filename = path.as_str();
-        for (index, token) in self.0.iter().enumerate() {
+        match self.segments {
+            Some(ref segments) => segments.applies_to(path.as_str()),
+            None => false,
+        }
+    }
+
+    /// Returns the literal text of the pattern without wildcards, and whether the pattern is anchored by `$`.
+    /// The pattern that is not anchored matches every path that starts with the text,
+    /// the anchored pattern matches only the path that is equal to the text.
+    pub(crate) fn get_literal(&self) -> Option<(&str, bool)> {
+        let segments = self.segments.as_ref()?;
+        match segments.parts.as_slice() {
+            [text] => Some((text, segments.anchored)),
+            [text, rest] if rest.is_empty() => Some((text, false)),
+            _ => None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        let mut length = 0;
+        for path_token in self.tokens.iter() {
+            length += path_token.len();
+        }
+        length
+    }
+}
+
+impl Segments {
+    /// Splits the tokens of the pattern at the wildcards.
+    /// Returns `None` for the pattern that has a text after `$`, because it never matches.
+    fn new(tokens: &[PathPatternToken]) -> Option<Segments> {
+        let mut parts = vec![String::new()];
+        let mut anchored = false;
+        for token in tokens.iter() {
             match *token {
+                PathPatternToken::Text(ref text) if anchored => {
+                    if !text.is_empty() {
+                        return None;
+                    }
+                }
                 PathPatternToken::Text(ref text) => {
-                    if !filename.starts_with(text) {
-                        return false;
+                    if let Some(part) = parts.last_mut() {
+                        part.push_str(text);
                     }
-                    filename = &filename[text.len()..];
                 }
                 PathPatternToken::AnyString => {
-                    if let Some(PathPatternToken::Text(ref text)) = self.0.get(index + 1) {
-                        while !filename.is_empty() {
-                            if filename.starts_with(text) {
-                                break;
-                            }
-                            // Search for next unicode char.
-                            if let Some((next_char_index, _)) = filename.char_indices().nth(1) {
-                                filename = &filename[next_char_index..];
-                            } else {
-                                break;
-                            }
-                        }
-                    } else {
-                        filename = &filename[filename.len()..];
+                    if !anchored {
+                        parts.push(String::new());
                     }
                 }
                 PathPatternToken::TerminateString => {
-                    if !filename.is_empty() {
-                        return false;
-                    }
+                    anchored = true;
                 }
             }
         }
-        true
+        Some(Segments { parts, anchored })
     }
 
-    pub fn len(&self) -> usize {
-        let mut length = 0;
-        for path_token in self.0.iter() {
-            length += path_token.len();
+    fn applies_to(&self, path: &str) -> bool {
+        let (first, rest) = match self.parts.split_first() {
+            Some(parts) => parts,
+            None => return true,
+        };
+        let mut path = match path.strip_prefix(first.as_str()) {
+            Some(path) => path,
+            None => return false,
+        };
+        let (last, middle) = match rest.split_last() {
+            Some(parts) => parts,
+            None => return !self.anchored || path.is_empty(),
+        };
+        if self.anchored {
+            path = match path.strip_suffix(last.as_str()) {
+                Some(path) => path,
+                None => return false,
+            };
         }
-        length
+        for part in middle.iter() {
+            match path.find(part.as_str()) {
+                Some(index) => path = &path[index + part.len()..],
+                None => return false,
+            }
+        }
+        self.anchored || path.contains(last.as_str())
     }
 }
 
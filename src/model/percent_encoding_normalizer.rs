@@ -0,0 +1,93 @@
+use std::borrow::Cow;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Normalizes the percent-encoding of the path or the path pattern as described in RFC 9309, section 2.2.2.
+/// Escaped unreserved characters are decoded, escaped reserved characters are kept encoded
+/// with upper case hex digits, and characters that are not allowed in the URI are encoded.
+/// Octets are never decoded into text, so paths that are not valid UTF-8 are compared byte for byte.
+/// The input that is already normalized is returned without allocation.
+pub(crate) fn normalize_percent_encoding(input: &str) -> Cow<'_, str> {
+    let mut position = match find_first_change(input.as_bytes()) {
+        Some(position) => position,
+        None => return Cow::Borrowed(input),
+    };
+    let bytes = input.as_bytes();
+    // Everything before the first change is ASCII, so the position is a char boundary.
+    let mut output = String::with_capacity(input.len() + 2);
+    output.push_str(&input[..position]);
+    while position < bytes.len() {
+        let byte = bytes[position];
+        if byte == b'%' {
+            if let Some(decoded) = decode_escape(&bytes[position + 1..]) {
+                if is_unreserved(decoded) {
+                    output.push(char::from(decoded));
+                } else {
+                    push_escape(&mut output, decoded);
+                }
+                position += 3;
+                continue;
+            }
+            // The `%` that does not start an escape is the same as the escaped `%`.
+            push_escape(&mut output, byte);
+        } else if is_unreserved(byte) || is_reserved(byte) {
+            output.push(char::from(byte));
+        } else {
+            push_escape(&mut output, byte);
+        }
+        position += 1;
+    }
+    Cow::Owned(output)
+}
+
+/// Returns the position of the first byte that is changed by the normalization.
+fn find_first_change(bytes: &[u8]) -> Option<usize> {
+    let mut position = 0;
+    while position < bytes.len() {
+        let byte = bytes[position];
+        if byte == b'%' {
+            match decode_escape(&bytes[position + 1..]) {
+                Some(decoded)
+                    if !is_unreserved(decoded)
+                        && !bytes[position + 1..position + 3].iter().any(u8::is_ascii_lowercase) =>
+                {
+                    position += 3;
+                }
+                _ => return Some(position),
+            }
+        } else if is_unreserved(byte) || is_reserved(byte) {
+            position += 1;
+        } else {
+            return Some(position);
+        }
+    }
+    None
+}
+
+/// Returns the octet of the escape whose `%` precedes the bytes.
+fn decode_escape(bytes: &[u8]) -> Option<u8> {
+    match bytes {
+        [high, low, ..] => Some(hex_value(*high)? << 4 | hex_value(*low)?),
+        _ => None,
+    }
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    char::from(byte).to_digit(16).map(|value| value as u8)
+}
+
+fn push_escape(output: &mut String, byte: u8) {
+    output.push('%');
+    output.push(char::from(HEX_DIGITS[usize::from(byte >> 4)]));
+    output.push(char::from(HEX_DIGITS[usize::from(byte & 0x0f)]));
+}
+
+/// Unreserved characters of RFC 3986, section 2.3.
+fn is_unreserved(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~')
+}
+
+/// Reserved characters of RFC 3986, section 2.2.
+fn is_reserved(byte: u8) -> bool {
+    b":/?#[]@!$&'()*+,;=".contains(&byte)
+}
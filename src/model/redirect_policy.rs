@@ -0,0 +1,92 @@
+use url::{Origin, Url};
+
+/// RFC 9309 requires crawlers to follow at least five consecutive redirects.
+const RFC9309_MAX_REDIRECTS: u32 = 5;
+
+/// Defines which redirects of the robots.txt request are followed to another origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossOriginRedirects {
+    /// Redirects to any origin are followed, as RFC 9309 requires.
+    Follow,
+    /// Redirects are followed only to the same host, e.g. from `http` to `https`.
+    SameHost,
+    /// Redirects to other origins are not followed.
+    Deny,
+}
+
+/// Defines how redirects of the robots.txt request are followed.
+///
+/// The rules of the robots.txt file that is reached by redirects apply to the initial origin.
+/// The redirect that is not followed is interpreted as the final response with its `3xx` status code.
+/// A redirect loop or more consecutive redirects than the maximum mean that the server is unreachable.
+///
+/// Redirects are followed by the fetchers of this crate only if the http client does not follow them itself,
+/// e.g. the reqwest client is created with `reqwest::redirect::Policy::none()`.
+/// Otherwise the redirects are followed by the client, which does not report the intermediate hops.
+/// The default policy accepts the response of the final url, like the previous versions of this crate.
+/// Other policies count the redirects of the client as a single redirect, so `max_redirects` does not limit
+/// the length of the chain, and only the final url is checked against the cross-origin rules.
+/// If such a policy does not allow the redirect, the fetch fails closed and the server is considered unreachable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedirectPolicy {
+    max_redirects: u32,
+    cross_origin: CrossOriginRedirects,
+}
+
+impl RedirectPolicy {
+    /// Creates a policy that does not follow redirects.
+    pub fn none() -> RedirectPolicy {
+        RedirectPolicy::new(0)
+    }
+
+    /// Creates a policy that follows up to five consecutive redirects to any origin, as RFC 9309 requires.
+    pub fn rfc9309() -> RedirectPolicy {
+        RedirectPolicy::new(RFC9309_MAX_REDIRECTS)
+    }
+
+    /// Creates a policy that follows up to `max_redirects` consecutive redirects to any origin.
+    pub fn new(max_redirects: u32) -> RedirectPolicy {
+        RedirectPolicy {
+            max_redirects,
+            cross_origin: CrossOriginRedirects::Follow,
+        }
+    }
+
+    /// Sets which redirects are followed to another origin.
+    pub fn with_cross_origin(mut self, cross_origin: CrossOriginRedirects) -> RedirectPolicy {
+        self.cross_origin = cross_origin;
+        self
+    }
+
+    /// Returns the maximum number of consecutive redirects.
+    pub fn get_max_redirects(&self) -> u32 {
+        self.max_redirects
+    }
+
+    /// Returns which redirects are followed to another origin.
+    pub fn get_cross_origin(&self) -> CrossOriginRedirects {
+        self.cross_origin
+    }
+
+    /// Checks that the redirect from the robots.txt file of the origin to the url may be followed.
+    pub fn is_allowed(&self, origin: &Origin, url: &Url) -> bool {
+        if self.max_redirects == 0 {
+            return false;
+        }
+        match self.cross_origin {
+            CrossOriginRedirects::Follow => true,
+            CrossOriginRedirects::SameHost => match *origin {
+                Origin::Tuple(_, ref host, _) => url.host().map(|url_host| url_host.to_owned()) == Some(host.clone()),
+                Origin::Opaque(..) => false,
+            },
+            CrossOriginRedirects::Deny => url.origin() == *origin,
+        }
+    }
+}
+
+/// The default policy does not follow redirects to keep the behaviour of the previous versions of this crate.
+impl Default for RedirectPolicy {
+    fn default() -> RedirectPolicy {
+        RedirectPolicy::none()
+    }
+}
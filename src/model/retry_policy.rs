@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(60);
+const DEFAULT_MULTIPLIER: u32 = 2;
+
+/// Defines how a robots.txt request is retried after a timeout, a connection failure,
+/// a `5xx` status code or the `429 Too Many Requests` status code.
+/// The `5xx` status codes that the `FetchPolicy` does not map to `FetchOutcome::Unreachable` are not retried,
+/// see `FetchPolicy::is_retryable_status_code`.
+///
+/// The delay before a retry grows exponentially from the initial backoff up to the maximum backoff.
+/// A `Retry-After` header of the response replaces the computed delay, but never exceeds the maximum backoff.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: u32,
+}
+
+impl RetryPolicy {
+    /// Creates a policy that does not retry requests.
+    pub fn none() -> RetryPolicy {
+        RetryPolicy::new(0)
+    }
+
+    /// Creates a policy that retries requests up to `max_retries` times.
+    /// The backoff starts at 1 second, doubles after every retry and is capped at 60 seconds.
+    pub fn new(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            multiplier: DEFAULT_MULTIPLIER,
+        }
+    }
+
+    /// Sets the delay before the first retry.
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> RetryPolicy {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the maximum delay before a retry.
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> RetryPolicy {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the factor the delay is multiplied by after every retry.
+    pub fn with_multiplier(mut self, multiplier: u32) -> RetryPolicy {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Returns the maximum number of retries.
+    pub fn get_max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
+    /// Returns the delay before the retry with the passed number, starting from 1.
+    /// The `retry_after` delay is the value of the `Retry-After` header of the failed response.
+    pub fn get_backoff(&self, retry: u32, retry_after: Option<Duration>) -> Duration {
+        let backoff = match retry_after {
+            Some(retry_after) => retry_after,
+            None => {
+                let factor = self.multiplier.saturating_pow(retry.saturating_sub(1));
+                self.initial_backoff.checked_mul(factor).unwrap_or(self.max_backoff)
+            }
+        };
+        backoff.min(self.max_backoff)
+    }
+}
+
+/// The default policy does not retry requests.
+impl Default for RetryPolicy {
+    fn default() -> RetryPolicy {
+        RetryPolicy::none()
+    }
+}
@@ -1,16 +1,29 @@
 use crate::model::clean_params::CleanParams;
+use crate::model::evaluation_mode::EvaluationMode;
 use crate::model::group::Group;
+use crate::model::source_span::SourceSpan;
+use crate::model::user_agent_matcher::UserAgentMatcher;
+use crate::user_agent::{extract_product_tokens, get_bare_product_token, AgentFamilies};
+use std::sync::Arc;
 use url::{Origin, Url};
 
 #[derive(Debug, Clone)]
 /// The robots.txt model that was obtained after parsing the text of the robots.txt file.
 /// To work with this model you should use the trait `robotparser::service::RobotsTxtService`.
 /// To create this structure you should use the `robotparser::parser::parse_robots_txt`.
+///
+/// The `can_fetch` check does not allocate when the user agent is a lowercase product token, e.g. `googlebot`
+/// or `googlebot/2.1`, and the percent-encoding of the URL path is already normalized.
+/// Other user agents, like full `User-Agent` header values, are split into product tokens on every check.
 pub struct RobotsTxt {
     origin: Origin,
     groups: Vec<Group>,
     sitemaps: Vec<Url>,
+    sitemap_spans: Vec<SourceSpan>,
     clean_params: Vec<CleanParams>,
+    evaluation_mode: EvaluationMode,
+    user_agent_matcher: Option<Arc<dyn UserAgentMatcher>>,
+    agent_families: Arc<AgentFamilies>,
 }
 
 impl RobotsTxt {
@@ -19,27 +32,87 @@ impl RobotsTxt {
             origin,
             groups: Vec::new(),
             sitemaps: Vec::new(),
+            sitemap_spans: Vec::new(),
             clean_params: Vec::new(),
+            evaluation_mode: EvaluationMode::default(),
+            user_agent_matcher: None,
+            agent_families: Arc::new(AgentFamilies::new()),
         }
     }
 
-    pub(crate) fn add_sitemap(&mut self, url: Url) {
+    /// Sets the mode used to evaluate the rules of this robots.txt file.
+    /// The default mode is `EvaluationMode::Legacy`.
+    pub fn set_evaluation_mode(&mut self, evaluation_mode: EvaluationMode) {
+        self.evaluation_mode = evaluation_mode;
+    }
+
+    /// Returns the mode used to evaluate the rules of this robots.txt file.
+    pub fn get_evaluation_mode(&self) -> EvaluationMode {
+        self.evaluation_mode
+    }
+
+    /// Sets the strategy used to find the groups that apply to a user agent.
+    /// The default strategy depends on the evaluation mode, see `EvaluationMode::get_default_user_agent_matcher`.
+    pub fn set_user_agent_matcher(&mut self, user_agent_matcher: impl UserAgentMatcher + 'static) {
+        self.user_agent_matcher = Some(Arc::new(user_agent_matcher));
+    }
+
+    pub(crate) fn set_shared_user_agent_matcher(&mut self, user_agent_matcher: Option<Arc<dyn UserAgentMatcher>>) {
+        self.user_agent_matcher = user_agent_matcher;
+    }
+
+    /// Returns the strategy used to find the groups that apply to a user agent.
+    pub fn get_user_agent_matcher(&self) -> &dyn UserAgentMatcher {
+        match self.user_agent_matcher {
+            Some(ref user_agent_matcher) => user_agent_matcher.as_ref(),
+            None => self.evaluation_mode.get_default_user_agent_matcher(),
+        }
+    }
+
+    /// Sets the fallback chains of crawler families that are walked before the `*` group is used.
+    /// By default no fallback chains are declared.
+    pub fn set_agent_families(&mut self, agent_families: AgentFamilies) {
+        self.agent_families = Arc::new(agent_families);
+    }
+
+    /// Returns the fallback chains of crawler families.
+    pub fn get_agent_families(&self) -> &AgentFamilies {
+        self.agent_families.as_ref()
+    }
+
+    pub(crate) fn add_sitemap(&mut self, url: Url, span: SourceSpan) {
         self.sitemaps.push(url);
+        self.sitemap_spans.push(span);
     }
 
     pub(crate) fn get_sitemaps_slice(&self) -> &[Url] {
         self.sitemaps.as_slice()
     }
 
+    /// Returns the locations of the `Sitemap` directives in the robots.txt file,
+    /// in the same order as the urls returned by `RobotsTxtService::get_sitemaps`.
+    pub fn get_sitemap_spans(&self) -> &[SourceSpan] {
+        self.sitemap_spans.as_slice()
+    }
+
     pub(crate) fn add_clean_params(&mut self, clean_params: CleanParams) {
         self.clean_params.push(clean_params);
     }
 
-    pub(crate) fn get_clean_params(&self) -> &[CleanParams] {
+    /// Returns the parameters of the `Clean-param` directives in order of appearance.
+    pub fn get_clean_params(&self) -> &[CleanParams] {
         self.clean_params.as_slice()
     }
 
-    pub(crate) fn add_group(&mut self, group: Group) {
+    /// Returns the groups of this robots.txt file.
+    /// Groups that share a user agent are merged into a single group as RFC 9309 requires
+    /// when the file was parsed in the `EvaluationMode::Rfc9309` mode.
+    pub fn get_groups(&self) -> &[Group] {
+        self.groups.as_slice()
+    }
+
+    pub(crate) fn add_group(&mut self, mut group: Group) {
+        group.build_index();
         self.groups.push(group);
     }
 
@@ -52,13 +125,30 @@ impl RobotsTxt {
         user_agent: &str,
         callback: impl Fn(&'a Group) -> Option<T>,
     ) -> Option<T> {
-        // Search by user agents
-        for group in self.groups.iter() {
-            if group.applies_to(user_agent) {
-                if let Some(output) = (callback)(group) {
-                    return Some(output);
+        if self.evaluation_mode == EvaluationMode::Rfc9309 {
+            let mut output = None;
+            self.for_each_rfc9309_group(user_agent, |group| {
+                if output.is_none() {
+                    output = (callback)(group);
                 }
+            });
+            return output;
+        }
+        // Search by user agents. Only the groups of the first product token that has groups are used.
+        let matcher = self.get_user_agent_matcher();
+        let output = self.find_by_product_token(user_agent, |product_token| {
+            if !self.groups.iter().any(|group| group.applies_to(product_token, matcher)) {
+                return None;
             }
+            Some(
+                self.groups
+                    .iter()
+                    .filter(|group| group.applies_to(product_token, matcher))
+                    .find_map(&callback),
+            )
+        });
+        if let Some(Some(output)) = output {
+            return Some(output);
         }
         if let Some(group) = self.get_default_group() {
             if let Some(output) = (callback)(group) {
@@ -68,12 +158,47 @@ impl RobotsTxt {
         None
     }
 
-    pub(crate) fn get_default_group(&self) -> Option<&Group> {
-        for group in self.groups.iter() {
-            if group.is_default() {
-                return Some(group);
+    /// Visits the groups that must be obeyed by the user agent according to RFC 9309.
+    /// These are the groups with the most specific user agent that applies to the passed one,
+    /// or all default groups when no other group applies.
+    pub(crate) fn for_each_rfc9309_group<'a>(&'a self, user_agent: &str, mut visit: impl FnMut(&'a Group)) {
+        let matcher = self.get_user_agent_matcher();
+        let found = self.find_by_product_token(user_agent, |product_token| {
+            let best_match_len = self
+                .groups
+                .iter()
+                .filter_map(|group| group.get_specific_match_len(product_token, matcher))
+                .max()?;
+            for group in self.groups.iter() {
+                if group.get_specific_match_len(product_token, matcher) == Some(best_match_len) {
+                    visit(group);
+                }
             }
+            Some(())
+        });
+        if found.is_none() {
+            self.groups.iter().filter(|group| group.is_default()).for_each(visit);
         }
-        None
+    }
+
+    /// Calls the callback with the product tokens of the user agent followed by their fallback tokens,
+    /// in order of priority, until it returns a result.
+    /// The user agent may be a bare product token or a full `User-Agent` header value.
+    /// The bare lowercase product token, e.g. `googlebot`, is passed without extracting the tokens into a new list.
+    fn find_by_product_token<T>(&self, user_agent: &str, mut callback: impl FnMut(&str) -> Option<T>) -> Option<T> {
+        if let Some(product_token) = get_bare_product_token(user_agent) {
+            let fallback_tokens = self.agent_families.get_fallback_tokens(product_token);
+            return std::iter::once(product_token)
+                .chain(fallback_tokens.iter().map(String::as_str))
+                .find_map(callback);
+        }
+        self.agent_families
+            .expand(&extract_product_tokens(user_agent))
+            .iter()
+            .find_map(|product_token| callback(product_token))
+    }
+
+    pub(crate) fn get_default_group(&self) -> Option<&Group> {
+        self.groups.iter().find(|group| group.is_default())
     }
 }
@@ -1,5 +1,6 @@
 use crate::model::path::Path;
 use crate::model::path_pattern::PathPattern;
+use crate::model::source_span::SourceSpan;
 
 /// A rule line is a single "Allow:" (allowance==True) or "Disallow:"
 /// (allowance==False) followed by a path."""
@@ -7,6 +8,7 @@ use crate::model::path_pattern::PathPattern;
 pub struct Rule {
     path_pattern: PathPattern,
     allowance: bool,
+    span: Option<SourceSpan>,
 }
 
 impl Rule {
@@ -14,17 +16,40 @@ impl Rule {
         Rule {
             path_pattern: path_pattern.into(),
             allowance,
+            span: None,
         }
     }
 
+    pub(crate) fn with_span(mut self, span: SourceSpan) -> Rule {
+        self.span = Some(span);
+        self
+    }
+
     pub(crate) fn applies_to(&self, path: &Path) -> bool {
         self.path_pattern.applies_to(path)
     }
 
-    pub(crate) fn get_allowance(&self) -> bool {
+    /// Returns true for the `Allow` rule and false for the `Disallow` rule.
+    pub fn get_allowance(&self) -> bool {
         self.allowance
     }
 
+    /// Returns the path pattern of the rule as it was written in the robots.txt file.
+    /// The empty `Disallow` directive allows everything and is represented by the `*` pattern of the `Allow` rule.
+    pub fn get_pattern(&self) -> &str {
+        self.path_pattern.as_str()
+    }
+
+    /// Returns the number of the line of the robots.txt file where the rule was defined.
+    pub fn get_line_no(&self) -> Option<usize> {
+        self.span.map(|span| span.get_line_no())
+    }
+
+    /// Returns the location of the rule in the robots.txt file.
+    pub fn get_span(&self) -> Option<SourceSpan> {
+        self.span
+    }
+
     pub(crate) fn get_path_pattern(&self) -> &PathPattern {
         &self.path_pattern
     }
@@ -0,0 +1,153 @@
+use crate::model::evaluation_mode::EvaluationMode;
+use crate::model::path::Path;
+use crate::model::rule::Rule;
+
+/// The rule of the group that applies to the path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RuleMatch {
+    /// Position of the rule in the group.
+    pub index: usize,
+    /// Length of the path pattern of the rule.
+    pub len: usize,
+    pub allowance: bool,
+}
+
+impl RuleMatch {
+    /// Checks that this match takes precedence over the other match in the evaluation mode.
+    /// The longest match always wins. When the length is the same, the first rule wins in the legacy mode,
+    /// and the `Allow` rule wins in the RFC 9309 mode.
+    pub(crate) fn is_better_than(&self, other: &RuleMatch, evaluation_mode: EvaluationMode) -> bool {
+        if self.len != other.len {
+            return self.len > other.len;
+        }
+        match evaluation_mode {
+            EvaluationMode::Legacy => self.index < other.index,
+            EvaluationMode::Rfc9309 => self.allowance && !other.allowance,
+        }
+    }
+}
+
+/// Node of the prefix trie of the literal path patterns.
+#[derive(Debug, Clone, Default)]
+struct Node {
+    /// Children of the node sorted by the byte of the path.
+    children: Vec<(u8, usize)>,
+    /// Rules whose pattern matches every path that starts with the text of this node.
+    prefix_rules: Vec<usize>,
+    /// Rules whose pattern matches only the path that is equal to the text of this node.
+    exact_rules: Vec<usize>,
+}
+
+/// Index of the rules of a group that is compiled when the robots.txt file is parsed.
+///
+/// Patterns without wildcards are kept in a prefix trie, so that all of them are checked
+/// in a single pass over the path. Patterns with wildcards are sorted by length,
+/// so that only the patterns that are longer than the best match so far are checked.
+/// Finding the rule does not allocate.
+#[derive(Debug, Clone)]
+pub(crate) struct RuleIndex {
+    nodes: Vec<Node>,
+    wildcard_rules: Vec<usize>,
+}
+
+impl RuleIndex {
+    pub(crate) fn new(rules: &[Rule]) -> RuleIndex {
+        let mut index = RuleIndex {
+            nodes: vec![Node::default()],
+            wildcard_rules: Vec::new(),
+        };
+        for (rule_index, rule) in rules.iter().enumerate() {
+            match rule.get_path_pattern().get_literal() {
+                Some((text, anchored)) => {
+                    let node = index.insert(text);
+                    if anchored {
+                        index.nodes[node].exact_rules.push(rule_index);
+                    } else {
+                        index.nodes[node].prefix_rules.push(rule_index);
+                    }
+                }
+                None => index.wildcard_rules.push(rule_index),
+            }
+        }
+        // The sort is stable, so rules of the same length keep their order.
+        index
+            .wildcard_rules
+            .sort_by_key(|rule_index| std::cmp::Reverse(rules[*rule_index].get_path_pattern().len()));
+        index
+    }
+
+    /// Returns the node of the text, adding the missing nodes.
+    fn insert(&mut self, text: &str) -> usize {
+        let mut node = 0;
+        for byte in text.bytes() {
+            node = match self.get_child(node, byte) {
+                Ok(child) => child,
+                Err(position) => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::default());
+                    self.nodes[node].children.insert(position, (byte, child));
+                    child
+                }
+            };
+        }
+        node
+    }
+
+    /// Returns the child of the node for the byte, or the position where the child must be inserted.
+    fn get_child(&self, node: usize, byte: u8) -> Result<usize, usize> {
+        let children = &self.nodes[node].children;
+        children
+            .binary_search_by_key(&byte, |(child_byte, _)| *child_byte)
+            .map(|position| children[position].1)
+    }
+
+    /// Returns the rule that takes precedence among the rules that apply to the path.
+    pub(crate) fn find(&self, rules: &[Rule], path: &Path, evaluation_mode: EvaluationMode) -> Option<RuleMatch> {
+        let mut best: Option<RuleMatch> = None;
+        let path_bytes = path.as_str().as_bytes();
+        let mut node = Some(0);
+        let mut depth = 0;
+        while let Some(current) = node {
+            let current_node = &self.nodes[current];
+            for rule_index in current_node.prefix_rules.iter() {
+                consider(&mut best, rules, *rule_index, evaluation_mode);
+            }
+            if depth == path_bytes.len() {
+                for rule_index in current_node.exact_rules.iter() {
+                    consider(&mut best, rules, *rule_index, evaluation_mode);
+                }
+                break;
+            }
+            node = self.get_child(current, path_bytes[depth]).ok();
+            depth += 1;
+        }
+        for rule_index in self.wildcard_rules.iter() {
+            let rule = &rules[*rule_index];
+            // Wildcard rules are sorted by length, so the remaining rules are shorter than the best match.
+            if matches!(best, Some(best) if rule.get_path_pattern().len() < best.len) {
+                break;
+            }
+            if rule.applies_to(path) {
+                consider(&mut best, rules, *rule_index, evaluation_mode);
+            }
+        }
+        best
+    }
+}
+
+/// Replaces the best match by the rule when the rule takes precedence.
+fn consider(best: &mut Option<RuleMatch>, rules: &[Rule], rule_index: usize, evaluation_mode: EvaluationMode) {
+    let rule = &rules[rule_index];
+    let candidate = RuleMatch {
+        index: rule_index,
+        len: rule.get_path_pattern().len(),
+        allowance: rule.get_allowance(),
+    };
+    let is_better = match *best {
+        Some(ref best) => candidate.is_better_than(best, evaluation_mode),
+        None => true,
+    };
+    if is_better {
+        *best = Some(candidate);
+    }
+}
@@ -0,0 +1,58 @@
+use std::ops::Range;
+
+/// Location of a directive or a group in the text of the robots.txt file.
+/// Byte offsets point into the original text, including the byte order mark, and exclude the line terminators.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceSpan {
+    line_no: usize,
+    end_line_no: usize,
+    start: usize,
+    end: usize,
+}
+
+impl SourceSpan {
+    /// Creates the span of the single line.
+    pub(crate) fn new(line_no: usize, start: usize, end: usize) -> SourceSpan {
+        SourceSpan {
+            line_no,
+            end_line_no: line_no,
+            start,
+            end,
+        }
+    }
+
+    /// Returns the span from the start of this span to the end of the other span.
+    pub(crate) fn to(self, other: SourceSpan) -> SourceSpan {
+        SourceSpan {
+            line_no: self.line_no,
+            end_line_no: other.end_line_no,
+            start: self.start,
+            end: other.end,
+        }
+    }
+
+    /// Returns the number of the first line of the span, starting from 1.
+    pub fn get_line_no(&self) -> usize {
+        self.line_no
+    }
+
+    /// Returns the number of the last line of the span, starting from 1.
+    pub fn get_end_line_no(&self) -> usize {
+        self.end_line_no
+    }
+
+    /// Returns the byte offset of the start of the span.
+    pub fn get_start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the byte offset of the end of the span, exclusive.
+    pub fn get_end(&self) -> usize {
+        self.end
+    }
+
+    /// Returns the byte range of the span, so that `&input[span.get_range()]` is the text of the span.
+    pub fn get_range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
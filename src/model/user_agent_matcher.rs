@@ -0,0 +1,45 @@
+use std::fmt::Debug;
+
+/// Strategy that decides whether a group of the robots.txt file applies to a crawler.
+///
+/// Both arguments are lowercased before the matcher is called.
+/// The `user_agent` argument is the product token of the crawler, e.g. `googlebot` for `Googlebot/2.1`.
+pub trait UserAgentMatcher: Debug + Send + Sync {
+    /// Returns true if the user agent of the group applies to the user agent of the crawler.
+    fn matches(&self, group_user_agent: &str, user_agent: &str) -> bool;
+}
+
+/// Matches the product token of the crawler exactly as RFC 9309 requires.
+/// This is the default in the `EvaluationMode::Rfc9309` mode.
+/// The `googlebot` group applies to `googlebot`, but does not apply to `googlebot-news`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProductTokenMatcher;
+
+impl UserAgentMatcher for ProductTokenMatcher {
+    fn matches(&self, group_user_agent: &str, user_agent: &str) -> bool {
+        group_user_agent == user_agent
+    }
+}
+
+/// Matches when the product token of the crawler starts with the user agent of the group.
+/// The `googlebot` group applies to `googlebot` and `googlebot-news`, but does not apply to `mygooglebot`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrefixMatcher;
+
+impl UserAgentMatcher for PrefixMatcher {
+    fn matches(&self, group_user_agent: &str, user_agent: &str) -> bool {
+        user_agent.starts_with(group_user_agent)
+    }
+}
+
+/// Matches when the user agent of the group is a substring of the user agent of the crawler.
+/// This is the legacy behaviour of this crate and it is used by default in the `EvaluationMode::Legacy` mode.
+/// The `bot` group applies to `googlebot`, `bingbot` and `robot`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SubstringMatcher;
+
+impl UserAgentMatcher for SubstringMatcher {
+    fn matches(&self, group_user_agent: &str, user_agent: &str) -> bool {
+        user_agent.contains(group_user_agent)
+    }
+}
@@ -1,6 +1,9 @@
 //! # Supported features and directives
 //!
 //! * Removes BOM unicode
+//! * Limits the number of lines, groups, rules and wildcards and the length of path patterns (see `ParserLimits`)
+//! * Merges groups that are declared for the same user agent in the RFC 9309 mode (see `ParserOptions`)
+//! * Accepts directives without `:` and paths without the leading `/` in the lenient mode (see `ParserOptions`)
 //! * Directive `User-Agent`
 //! * Directive `Allow`
 //! * Directive `Disallow`
@@ -27,6 +30,12 @@
 //! ```
 mod robots_txt_parser;
 pub use self::robots_txt_parser::parse as parse_robots_txt;
+pub use self::robots_txt_parser::parse_with_limits as parse_robots_txt_with_limits;
+pub use self::robots_txt_parser::parse_with_options as parse_robots_txt_with_options;
+mod parser_limits;
+pub use self::parser_limits::ParserLimits;
+mod parser_options;
+pub use self::parser_options::{ExtensionDirective, ParserOptions};
 mod warning_reason;
 pub use self::warning_reason::WarningReason;
 mod warning;
@@ -35,4 +44,8 @@ mod parse_result;
 pub use self::parse_result::ParseResult;
 mod fetched_robots_txt_parser;
 pub use self::fetched_robots_txt_parser::parse as parse_fetched_robots_txt;
+pub use self::fetched_robots_txt_parser::parse_with_metadata as parse_fetched_robots_txt_with_metadata;
+pub use self::fetched_robots_txt_parser::parse_with_options as parse_fetched_robots_txt_with_options;
+pub use self::fetched_robots_txt_parser::parse_with_policy as parse_fetched_robots_txt_with_policy;
+pub use self::fetched_robots_txt_parser::parse_with_previous as parse_fetched_robots_txt_with_previous;
 mod line;
@@ -1,20 +1,124 @@
-use crate::model::{FetchedRobotsTxt, FetchedRobotsTxtContainer};
-use crate::parser::parse_robots_txt;
+use crate::model::{FetchMetadata, FetchOutcome, FetchPolicy, FetchedRobotsTxt, FetchedRobotsTxtContainer};
+use crate::parser::line::Line;
+use crate::parser::parse_robots_txt_with_options;
+use crate::parser::parser_options::ParserOptions;
+use crate::parser::warning::ParseWarning;
 use crate::parser::ParseResult;
 use url::Origin;
 
-const UNAUTHORIZED: u16 = 401;
-const FORBIDDEN: u16 = 403;
-const OK: u16 = 200;
+const NOT_MODIFIED: u16 = 304;
 
 /// Parses the text of the robots.txt file located in the specified place of origin,
 /// taking into account the response status code of the HTTP-request.
+/// The status code is interpreted with the default `FetchPolicy`.
 /// **IMPORTANT NOTE**: origin must point to robots.txt url **before redirects**.
 pub fn parse(origin: Origin, status_code: u16, input: &str) -> ParseResult<FetchedRobotsTxt> {
-    match status_code {
-        UNAUTHORIZED | FORBIDDEN => ParseResult::new(FetchedRobotsTxt::new(FetchedRobotsTxtContainer::FetchDenied)),
-        OK => parse_robots_txt(origin, input)
-            .map(|result| FetchedRobotsTxt::new(FetchedRobotsTxtContainer::Fetched(result))),
-        _ => ParseResult::new(FetchedRobotsTxt::new(FetchedRobotsTxtContainer::FetchFailed)),
+    parse_with_policy(origin, status_code, input, &FetchPolicy::default())
+}
+
+/// Parses the text of the robots.txt file located in the specified place of origin,
+/// taking into account the response status code of the HTTP-request.
+/// The status code is interpreted with the passed `FetchPolicy`.
+/// **IMPORTANT NOTE**: origin must point to robots.txt url **before redirects**.
+pub fn parse_with_policy(
+    origin: Origin,
+    status_code: u16,
+    input: &str,
+    policy: &FetchPolicy,
+) -> ParseResult<FetchedRobotsTxt> {
+    parse_with_metadata(origin, FetchMetadata::new(status_code), input, policy)
+}
+
+/// Parses the text of the robots.txt file located in the specified place of origin,
+/// taking into account the response status code of the HTTP-request.
+/// The status code is interpreted with the default `FetchPolicy`, and the text is parsed with the passed options.
+/// **IMPORTANT NOTE**: origin must point to robots.txt url **before redirects**.
+pub fn parse_with_options(
+    origin: Origin,
+    status_code: u16,
+    input: &str,
+    options: &ParserOptions,
+) -> ParseResult<FetchedRobotsTxt> {
+    let policy = FetchPolicy::default().with_parser_options(options.clone());
+    parse_with_policy(origin, status_code, input, &policy)
+}
+
+/// Parses the response to the request that revalidated the previously fetched robots.txt file.
+/// The `304 Not Modified` response keeps the parsed model of the previous robots.txt file and extends its lifetime.
+/// Other responses are parsed as `parse_with_metadata` does, and the outage state is carried over
+/// from the previous result (see `FetchedRobotsTxt::with_previous`).
+/// **IMPORTANT NOTE**: origin must point to robots.txt url **before redirects**.
+pub fn parse_with_previous(
+    origin: Origin,
+    metadata: FetchMetadata,
+    input: &str,
+    policy: &FetchPolicy,
+    previous: &FetchedRobotsTxt,
+) -> ParseResult<FetchedRobotsTxt> {
+    if metadata.get_status_code() == Some(NOT_MODIFIED) {
+        return ParseResult::new(previous.revalidated(metadata));
+    }
+    parse_with_metadata(origin, metadata, input, policy).map(|result| result.with_previous(previous))
+}
+
+/// Parses the text of the robots.txt file located in the specified place of origin,
+/// taking into account the status code and the headers of the HTTP-response.
+/// The status code is interpreted with the passed `FetchPolicy`, and the text is parsed with its `ParserOptions`.
+/// Metadata without a status code is interpreted as a network error.
+/// The text that exceeds the maximum body size of the policy is cut off at the end of the last complete line,
+/// and the `BodyTruncated` warning is added to the result.
+/// The size and the hash of the text are recorded in the metadata unless it already has them.
+/// **IMPORTANT NOTE**: origin must point to robots.txt url **before redirects**.
+pub fn parse_with_metadata(
+    origin: Origin,
+    metadata: FetchMetadata,
+    input: &str,
+    policy: &FetchPolicy,
+) -> ParseResult<FetchedRobotsTxt> {
+    let (input, warning) = truncate(input, policy.get_max_body_size());
+    let metadata = match metadata.get_status_code() {
+        Some(_) if metadata.get_body_size().is_none() => metadata.with_body(input),
+        _ => metadata,
+    };
+    let outcome = match metadata.get_status_code() {
+        Some(status_code) => policy.get_outcome(status_code),
+        None => policy.get_network_error_outcome().unwrap_or(FetchOutcome::Unreachable),
+    };
+    match outcome {
+        FetchOutcome::DisallowAll => {
+            ParseResult::new(FetchedRobotsTxt::new(metadata, FetchedRobotsTxtContainer::FetchDenied))
+        }
+        FetchOutcome::Parse => {
+            let result = parse_robots_txt_with_options(origin, input, policy.get_parser_options()).map(|result| {
+                FetchedRobotsTxt::new(metadata, FetchedRobotsTxtContainer::Fetched(result)).with_body(input)
+            });
+            match warning {
+                Some(warning) => result.with_warning(warning),
+                None => result,
+            }
+        }
+        FetchOutcome::AllowAll => {
+            ParseResult::new(FetchedRobotsTxt::new(metadata, FetchedRobotsTxtContainer::FetchFailed))
+        }
+        FetchOutcome::Unreachable => ParseResult::new(FetchedRobotsTxt::unreachable(
+            metadata,
+            policy.get_unreachable_timeout(),
+        )),
+    }
+}
+
+/// Cuts off the text of the robots.txt file that exceeds the maximum size at the end of the last complete line.
+/// Returns the remaining text and the warning about the line that was cut.
+fn truncate(input: &str, max_size: usize) -> (&str, Option<ParseWarning>) {
+    if input.len() <= max_size {
+        return (input, None);
+    }
+    let mut end = max_size;
+    while !input.is_char_boundary(end) {
+        end -= 1;
     }
+    let kept = input[..end].rfind('\n').map_or(0, |index| index + 1);
+    let line_no = input[..kept].lines().count() + 1;
+    let line = Line::new(input[kept..end].trim_end_matches('\r'), line_no);
+    (&input[..kept], Some(ParseWarning::body_truncated(&line, max_size)))
 }
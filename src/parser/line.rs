@@ -1,11 +1,24 @@
+use crate::model::SourceSpan;
+
 pub struct Line<'a> {
     line: &'a str,
     position: usize,
+    offset: usize,
 }
 
 impl<'a> Line<'a> {
     pub fn new(line: &'a str, position: usize) -> Line<'a> {
-        Line { line, position }
+        Line {
+            line,
+            position,
+            offset: 0,
+        }
+    }
+
+    /// Sets the byte offset of the line in the text of the robots.txt file.
+    pub fn with_offset(mut self, offset: usize) -> Line<'a> {
+        self.offset = offset;
+        self
     }
 
     pub fn get_line_text(&self) -> &str {
@@ -15,4 +28,8 @@ impl<'a> Line<'a> {
     pub fn get_line_number(&self) -> usize {
         self.position
     }
+
+    pub fn get_span(&self) -> SourceSpan {
+        SourceSpan::new(self.position, self.offset, self.offset + self.line.len())
+    }
 }
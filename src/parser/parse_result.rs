@@ -28,6 +28,12 @@ where
         ParseResult { result, warnings }
     }
 
+    /// Adds the warning after the warnings of the parser.
+    pub(crate) fn with_warning(mut self, warning: ParseWarning) -> ParseResult<R> {
+        self.warnings.push(warning);
+        self
+    }
+
     /// Returns the result of the robots.txt parser.
     pub fn get_result(self) -> R {
         self.result
@@ -56,7 +62,7 @@ where
     }
 
     /// Converts this structure into another type of structure.
-    pub(crate) fn map<T>(self, callback: impl Fn(R) -> T) -> ParseResult<T>
+    pub(crate) fn map<T>(self, callback: impl FnOnce(R) -> T) -> ParseResult<T>
     where
         T: Debug,
     {
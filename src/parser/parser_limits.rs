@@ -0,0 +1,140 @@
+/// Lines of the robots.txt file that are parsed by default.
+const DEFAULT_MAX_LINES: usize = 100_000;
+/// Rules that are kept in a group by default.
+const DEFAULT_MAX_RULES_PER_GROUP: usize = 10_000;
+/// Groups that are kept in the robots.txt file by default.
+const DEFAULT_MAX_GROUPS: usize = 10_000;
+/// Rules that are kept in all groups of the robots.txt file by default.
+const DEFAULT_MAX_RULES: usize = 100_000;
+/// Length of the path pattern in bytes that is accepted by default.
+const DEFAULT_MAX_PATTERN_LENGTH: usize = 2048;
+/// Number of `*` wildcards in a path pattern that is accepted by default.
+const DEFAULT_MAX_WILDCARDS_PER_PATTERN: usize = 16;
+
+/// Limits of the robots.txt parser that protect crawlers against pathological robots.txt files.
+///
+/// Lines after the maximum number of lines are ignored. Rules that exceed the maximum number of rules
+/// of a group, or whose path pattern is too long or has too many wildcards, are ignored.
+/// Groups after the maximum number of groups, and rules after the maximum number of rules of all groups,
+/// are ignored too. The rules that are copied into the groups merged in the `EvaluationMode::Rfc9309` mode
+/// count against both limits of rules.
+/// Every limit that is exceeded is reported by a warning of the parser.
+///
+/// # Example
+/// ```rust
+/// use robotparser::parser::{parse_robots_txt_with_limits, ParserLimits, WarningReason};
+/// use url::Url;
+///
+/// let origin = Url::parse("https://example.com/robots.txt").unwrap().origin();
+/// let limits = ParserLimits::default().with_max_wildcards_per_pattern(2);
+/// let result = parse_robots_txt_with_limits(origin, "User-agent: *\nDisallow: /*a*b*c", &limits);
+/// assert!(matches!(
+///     result.get_warnings()[0].get_reason(),
+///     WarningReason::TooManyWildcards { max_wildcards: 2 }
+/// ));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParserLimits {
+    max_lines: usize,
+    max_rules_per_group: usize,
+    max_groups: usize,
+    max_rules: usize,
+    max_pattern_length: usize,
+    max_wildcards_per_pattern: usize,
+}
+
+impl ParserLimits {
+    /// Creates limits that never apply.
+    pub fn unlimited() -> ParserLimits {
+        ParserLimits {
+            max_lines: usize::MAX,
+            max_rules_per_group: usize::MAX,
+            max_groups: usize::MAX,
+            max_rules: usize::MAX,
+            max_pattern_length: usize::MAX,
+            max_wildcards_per_pattern: usize::MAX,
+        }
+    }
+
+    /// Sets the maximum number of lines that are parsed.
+    pub fn with_max_lines(mut self, max_lines: usize) -> ParserLimits {
+        self.max_lines = max_lines;
+        self
+    }
+
+    /// Sets the maximum number of `Allow` and `Disallow` rules of a group.
+    pub fn with_max_rules_per_group(mut self, max_rules_per_group: usize) -> ParserLimits {
+        self.max_rules_per_group = max_rules_per_group;
+        self
+    }
+
+    /// Sets the maximum number of groups of the robots.txt file.
+    pub fn with_max_groups(mut self, max_groups: usize) -> ParserLimits {
+        self.max_groups = max_groups;
+        self
+    }
+
+    /// Sets the maximum number of `Allow` and `Disallow` rules of all groups of the robots.txt file.
+    pub fn with_max_rules(mut self, max_rules: usize) -> ParserLimits {
+        self.max_rules = max_rules;
+        self
+    }
+
+    /// Sets the maximum length of the path pattern in bytes.
+    pub fn with_max_pattern_length(mut self, max_pattern_length: usize) -> ParserLimits {
+        self.max_pattern_length = max_pattern_length;
+        self
+    }
+
+    /// Sets the maximum number of `*` wildcards in the path pattern.
+    pub fn with_max_wildcards_per_pattern(mut self, max_wildcards_per_pattern: usize) -> ParserLimits {
+        self.max_wildcards_per_pattern = max_wildcards_per_pattern;
+        self
+    }
+
+    /// Returns the maximum number of lines that are parsed.
+    pub fn get_max_lines(&self) -> usize {
+        self.max_lines
+    }
+
+    /// Returns the maximum number of `Allow` and `Disallow` rules of a group.
+    pub fn get_max_rules_per_group(&self) -> usize {
+        self.max_rules_per_group
+    }
+
+    /// Returns the maximum number of groups of the robots.txt file.
+    pub fn get_max_groups(&self) -> usize {
+        self.max_groups
+    }
+
+    /// Returns the maximum number of `Allow` and `Disallow` rules of all groups of the robots.txt file.
+    pub fn get_max_rules(&self) -> usize {
+        self.max_rules
+    }
+
+    /// Returns the maximum length of the path pattern in bytes.
+    pub fn get_max_pattern_length(&self) -> usize {
+        self.max_pattern_length
+    }
+
+    /// Returns the maximum number of `*` wildcards in the path pattern.
+    pub fn get_max_wildcards_per_pattern(&self) -> usize {
+        self.max_wildcards_per_pattern
+    }
+}
+
+/// The default limits are far above the needs of real robots.txt files:
+/// 100000 lines, 10000 rules per group, 10000 groups, 100000 rules in total,
+/// path patterns of 2048 bytes with 16 wildcards.
+impl Default for ParserLimits {
+    fn default() -> ParserLimits {
+        ParserLimits {
+            max_lines: DEFAULT_MAX_LINES,
+            max_rules_per_group: DEFAULT_MAX_RULES_PER_GROUP,
+            max_groups: DEFAULT_MAX_GROUPS,
+            max_rules: DEFAULT_MAX_RULES,
+            max_pattern_length: DEFAULT_MAX_PATTERN_LENGTH,
+            max_wildcards_per_pattern: DEFAULT_MAX_WILDCARDS_PER_PATTERN,
+        }
+    }
+}
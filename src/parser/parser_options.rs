@@ -0,0 +1,122 @@
+use crate::model::{EvaluationMode, UserAgentMatcher};
+use crate::parser::parser_limits::ParserLimits;
+use std::sync::Arc;
+
+/// Directives of the robots.txt file that are not part of RFC 9309 and can be turned off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtensionDirective {
+    /// The `Sitemap` directive.
+    Sitemap,
+    /// The `Crawl-Delay` directive.
+    CrawlDelay,
+    /// The `Request-Rate` directive.
+    RequestRate,
+    /// The `Clean-Param` directive.
+    CleanParam,
+}
+
+/// Options of the robots.txt parser.
+///
+/// The default options keep the behaviour of `parse_robots_txt`: all directives are recognised,
+/// path patterns must start with `/` or `*`, the default `ParserLimits` apply,
+/// and the parsed model uses `EvaluationMode::Legacy` with the `SubstringMatcher`.
+/// Unless a matcher is set, the `EvaluationMode::Rfc9309` mode uses the `ProductTokenMatcher`.
+///
+/// # Example
+/// ```rust
+/// use robotparser::model::{EvaluationMode, ProductTokenMatcher};
+/// use robotparser::parser::{parse_robots_txt_with_options, ExtensionDirective, ParserOptions};
+/// use robotparser::service::RobotsTxtService;
+/// use url::Url;
+///
+/// let options = ParserOptions::default()
+///     .with_lenient(true)
+///     .with_directive(ExtensionDirective::CleanParam, false)
+///     .with_evaluation_mode(EvaluationMode::Rfc9309)
+///     .with_user_agent_matcher(ProductTokenMatcher);
+/// let origin = Url::parse("https://example.com/robots.txt").unwrap().origin();
+/// let result = parse_robots_txt_with_options(origin, "User-agent: *\nDisallow private", &options);
+/// let robots_txt = result.get_result();
+/// assert!(!robots_txt.can_fetch("*", &Url::parse("https://example.com/private").unwrap()));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ParserOptions {
+    limits: ParserLimits,
+    lenient: bool,
+    disabled_directives: Vec<ExtensionDirective>,
+    evaluation_mode: EvaluationMode,
+    user_agent_matcher: Option<Arc<dyn UserAgentMatcher>>,
+}
+
+impl ParserOptions {
+    /// Sets the limits of the parser.
+    pub fn with_limits(mut self, limits: ParserLimits) -> ParserOptions {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets whether the parser accepts common mistakes of robots.txt files like crawlers of search engines do:
+    /// directives whose key and value are separated by whitespace instead of `:`,
+    /// and path patterns without the leading `/`. By default the parser is strict and warns about them.
+    pub fn with_lenient(mut self, lenient: bool) -> ParserOptions {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Sets whether the directive is recognised. Directives that are not recognised
+    /// are reported by the `UnsupportedDirectiveKey` warning and ignored.
+    pub fn with_directive(mut self, directive: ExtensionDirective, enabled: bool) -> ParserOptions {
+        self.disabled_directives.retain(|item| *item != directive);
+        if !enabled {
+            self.disabled_directives.push(directive);
+        }
+        self
+    }
+
+    /// Sets the mode used to evaluate the rules of the parsed robots.txt file.
+    /// In the `EvaluationMode::Rfc9309` mode, the records that share a user agent are also merged
+    /// into a single group, and each merge is reported by the `GroupMerged` warning.
+    pub fn with_evaluation_mode(mut self, evaluation_mode: EvaluationMode) -> ParserOptions {
+        self.evaluation_mode = evaluation_mode;
+        self
+    }
+
+    /// Sets the strategy used to find the groups of the parsed robots.txt file that apply to a user agent.
+    /// The default strategy depends on the evaluation mode, see `EvaluationMode::get_default_user_agent_matcher`.
+    pub fn with_user_agent_matcher(mut self, user_agent_matcher: impl UserAgentMatcher + 'static) -> ParserOptions {
+        self.user_agent_matcher = Some(Arc::new(user_agent_matcher));
+        self
+    }
+
+    /// Returns the limits of the parser.
+    pub fn get_limits(&self) -> &ParserLimits {
+        &self.limits
+    }
+
+    /// Checks that the parser accepts common mistakes of robots.txt files.
+    pub fn is_lenient(&self) -> bool {
+        self.lenient
+    }
+
+    /// Checks that the directive is recognised.
+    pub fn is_directive_enabled(&self, directive: ExtensionDirective) -> bool {
+        !self.disabled_directives.contains(&directive)
+    }
+
+    /// Returns the mode used to evaluate the rules of the parsed robots.txt file.
+    pub fn get_evaluation_mode(&self) -> EvaluationMode {
+        self.evaluation_mode
+    }
+
+    /// Returns the strategy used to find the groups of the parsed robots.txt file that apply to a user agent.
+    pub fn get_user_agent_matcher(&self) -> &dyn UserAgentMatcher {
+        match self.user_agent_matcher {
+            Some(ref user_agent_matcher) => user_agent_matcher.as_ref(),
+            None => self.evaluation_mode.get_default_user_agent_matcher(),
+        }
+    }
+
+    pub(crate) fn get_shared_user_agent_matcher(&self) -> Option<Arc<dyn UserAgentMatcher>> {
+        self.user_agent_matcher.clone()
+    }
+}
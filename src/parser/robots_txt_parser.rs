@@ -1,7 +1,10 @@
-use crate::model::{CleanParams, PathPattern, RequestRate, RobotsTxt, Rule};
+use crate::model::{CleanParams, Group, PathPattern, RequestRate, RobotsTxt, Rule};
 use crate::parser::line::Line;
 use crate::parser::parse_result::ParseResult;
+use crate::parser::parser_limits::ParserLimits;
+use crate::parser::parser_options::{ExtensionDirective, ParserOptions};
 use crate::parser::warning::ParseWarning;
+use std::borrow::Cow;
 use std::time::Duration;
 use url::{Origin, Url};
 mod directive;
@@ -13,8 +16,20 @@ const COMMENT_BEGIN_CHAR: char = '#';
 const KV_SEPARATOR: &str = ":";
 
 /// Parses the text of the robots.txt file located in the specified origin.
+/// The default `ParserOptions` apply.
 pub fn parse(origin: Origin, input: &str) -> ParseResult<RobotsTxt> {
-    let parser = Parser::new(origin);
+    parse_with_options(origin, input, &ParserOptions::default())
+}
+
+/// Parses the text of the robots.txt file located in the specified origin.
+/// Lines and rules that exceed the limits are ignored with a warning.
+pub fn parse_with_limits(origin: Origin, input: &str, limits: &ParserLimits) -> ParseResult<RobotsTxt> {
+    parse_with_options(origin, input, &ParserOptions::default().with_limits(limits.clone()))
+}
+
+/// Parses the text of the robots.txt file located in the specified origin with the options.
+pub fn parse_with_options(origin: Origin, input: &str, options: &ParserOptions) -> ParseResult<RobotsTxt> {
+    let parser = Parser::new(origin, options.clone());
     parser.parse(input)
 }
 
@@ -22,22 +37,38 @@ struct Parser {
     result: RobotsTxt,
     group_builder: GroupBuilder,
     warnings: Vec<ParseWarning>,
+    options: ParserOptions,
 }
 
 impl Parser {
-    pub fn new(origin: Origin) -> Parser {
+    pub fn new(origin: Origin, options: ParserOptions) -> Parser {
+        let mut result = RobotsTxt::new(origin);
+        result.set_evaluation_mode(options.get_evaluation_mode());
+        result.set_shared_user_agent_matcher(options.get_shared_user_agent_matcher());
         Parser {
-            result: RobotsTxt::new(origin),
+            result,
             group_builder: GroupBuilder::new(),
             warnings: Vec::new(),
+            options,
         }
     }
 
     pub fn parse(mut self, input: &str) -> ParseResult<RobotsTxt> {
-        let input = ignore_bom(input);
-        for (line_no, line) in input.lines().enumerate() {
-            let line = Line::new(line, line_no + 1);
-            match Self::parse_line(&line) {
+        let text = ignore_bom(input);
+        let mut offset = input.len() - text.len();
+        let max_lines = self.options.get_limits().get_max_lines();
+        for (line_no, line) in text.split_inclusive('\n').enumerate() {
+            let line_offset = offset;
+            offset += line.len();
+            let line = line
+                .strip_suffix('\n')
+                .map_or(line, |line| line.strip_suffix('\r').unwrap_or(line));
+            let line = Line::new(line, line_no + 1).with_offset(line_offset);
+            if line_no >= max_lines {
+                self.warnings.push(ParseWarning::too_many_lines(&line, max_lines));
+                break;
+            }
+            match Self::parse_line(&line, self.options.is_lenient()) {
                 Ok(Some(line_value)) => {
                     self.process_line_value(&line, &line_value);
                 }
@@ -47,11 +78,12 @@ impl Parser {
                 _ => {}
             }
         }
-        self.group_builder.fill_entries(&mut self.result);
+        let merge_warnings = self.group_builder.fill_entries(input, &mut self.result, &self.options);
+        self.warnings.extend(merge_warnings);
         ParseResult::new_with_warnings(self.result, self.warnings)
     }
 
-    fn parse_line<'a>(line: &'a Line) -> Result<Option<Directive<'a>>, ParseWarning> {
+    fn parse_line<'a>(line: &'a Line, lenient: bool) -> Result<Option<Directive<'a>>, ParseWarning> {
         let mut kv_part = line.get_line_text();
         if let Some(comment_separator_position) = line.get_line_text().find(COMMENT_BEGIN_CHAR) {
             kv_part = &kv_part[0..comment_separator_position];
@@ -59,9 +91,11 @@ impl Parser {
         if kv_part.is_empty() {
             return Ok(None);
         }
-        let separator_index = kv_part
-            .find(KV_SEPARATOR)
-            .ok_or_else(|| ParseWarning::invalid_directive_format(line))?;
+        let separator_index = match kv_part.find(KV_SEPARATOR) {
+            Some(separator_index) => separator_index,
+            None if lenient => return Self::parse_line_without_separator(line, kv_part),
+            None => return Err(ParseWarning::invalid_directive_format(line)),
+        };
         if separator_index >= kv_part.len() {
             return Err(ParseWarning::invalid_directive_format(line));
         }
@@ -76,6 +110,20 @@ impl Parser {
         Ok(Some(result))
     }
 
+    /// Parses the directive whose key and value are separated by whitespace.
+    fn parse_line_without_separator<'a>(
+        line: &'a Line,
+        kv_part: &'a str,
+    ) -> Result<Option<Directive<'a>>, ParseWarning> {
+        let kv_part = kv_part.trim();
+        let separator_index = kv_part
+            .find(char::is_whitespace)
+            .ok_or_else(|| ParseWarning::invalid_directive_format(line))?;
+        let key = &kv_part[0..separator_index];
+        let value = kv_part[separator_index..].trim();
+        Ok(Some(Directive::new(key, value)))
+    }
+
     fn process_line_value(&mut self, line: &Line, directive: &Directive) {
         let key = directive.get_key_lowercase();
         match key.as_str() {
@@ -89,17 +137,17 @@ impl Parser {
             "disallow" => {
                 self.process_directive_disallow(line, directive);
             }
-            "crawl-delay" => {
+            "crawl-delay" if self.options.is_directive_enabled(ExtensionDirective::CrawlDelay) => {
                 self.process_directive_crawl_delay(line, directive);
             }
-            "request-rate" => {
+            "request-rate" if self.options.is_directive_enabled(ExtensionDirective::RequestRate) => {
                 self.process_directive_request_rate(line, directive);
             }
             // Non-group directives
-            "sitemap" => {
+            "sitemap" if self.options.is_directive_enabled(ExtensionDirective::Sitemap) => {
                 self.process_directive_sitemap(line, directive);
             }
-            "clean-param" => {
+            "clean-param" if self.options.is_directive_enabled(ExtensionDirective::CleanParam) => {
                 self.process_directive_clean_param(line, directive);
             }
             _ => {
@@ -114,15 +162,19 @@ impl Parser {
             self.warnings.push(ParseWarning::user_agent_cannot_be_empty(line));
             return;
         }
-        self.group_builder.handle_user_agent(user_agent);
+        self.group_builder.handle_user_agent(line, user_agent);
     }
 
     fn process_directive_allow(&mut self, line: &Line, directive: &Directive) {
-        if let Some(group) = self.group_builder.get_mut_active_group() {
+        let limits = self.options.get_limits();
+        let path = get_path(directive.get_value(), self.options.is_lenient());
+        if let Some(group) = self.group_builder.get_mut_active_group(line) {
             if directive.get_value() == "" {
                 // Nothing to do. Ignoring.
-            } else if directive.get_value().starts_with('*') || directive.get_value().starts_with('/') {
-                group.push_rule(Rule::new(directive.get_value(), true));
+            } else if let Some(path) = path {
+                let rule = check_path_pattern(line, &path, limits)
+                    .and_then(|_| push_rule(group, line, Rule::new(&*path, true), limits));
+                self.warnings.extend(rule.err());
             } else {
                 self.warnings.push(ParseWarning::wrong_path_format(line));
             }
@@ -132,12 +184,17 @@ impl Parser {
     }
 
     fn process_directive_disallow(&mut self, line: &Line, directive: &Directive) {
-        if let Some(group) = self.group_builder.get_mut_active_group() {
+        let limits = self.options.get_limits();
+        let path = get_path(directive.get_value(), self.options.is_lenient());
+        if let Some(group) = self.group_builder.get_mut_active_group(line) {
             if directive.get_value() == "" {
                 // Allow all.
-                group.push_rule(Rule::new(PathPattern::all(), true));
-            } else if directive.get_value().starts_with('*') || directive.get_value().starts_with('/') {
-                group.push_rule(Rule::new(directive.get_value(), false));
+                let rule = push_rule(group, line, Rule::new(PathPattern::all(), true), limits);
+                self.warnings.extend(rule.err());
+            } else if let Some(path) = path {
+                let rule = check_path_pattern(line, &path, limits)
+                    .and_then(|_| push_rule(group, line, Rule::new(&*path, false), limits));
+                self.warnings.extend(rule.err());
             } else {
                 self.warnings.push(ParseWarning::wrong_path_format(line));
             }
@@ -147,13 +204,13 @@ impl Parser {
     }
 
     fn process_directive_crawl_delay(&mut self, line: &Line, directive: &Directive) {
-        if let Some(group) = self.group_builder.get_mut_active_group() {
+        if let Some(group) = self.group_builder.get_mut_active_group(line) {
             match directive.get_value().parse::<f64>() {
                 Ok(delay) => {
                     let delay_seconds = delay.trunc();
                     let delay_nanoseconds = delay.fract() * 10f64.powi(9);
                     let delay = Duration::new(delay_seconds as u64, delay_nanoseconds as u32);
-                    group.set_crawl_delay(delay);
+                    group.set_crawl_delay(delay, line.get_span());
                 }
                 Err(error) => {
                     self.warnings.push(ParseWarning::parse_crawl_delay_error(line, error));
@@ -165,7 +222,7 @@ impl Parser {
     }
 
     fn process_directive_request_rate(&mut self, line: &Line, directive: &Directive) {
-        if let Some(group) = self.group_builder.get_mut_active_group() {
+        if let Some(group) = self.group_builder.get_mut_active_group(line) {
             let numbers: Vec<&str> = directive.get_value().split('/').collect();
             if numbers.len() != 2 {
                 self.warnings.push(ParseWarning::wrong_request_rate_format(line));
@@ -185,7 +242,7 @@ impl Parser {
                     return;
                 }
             };
-            group.set_req_rate(RequestRate { requests, seconds });
+            group.set_req_rate(RequestRate { requests, seconds }, line.get_span());
         } else {
             self.warnings.push(ParseWarning::directive_without_user_agent(line));
         }
@@ -194,7 +251,7 @@ impl Parser {
     fn process_directive_sitemap(&mut self, line: &Line, directive: &Directive) {
         match Url::parse(directive.get_value()) {
             Ok(sitemap_url) => {
-                self.result.add_sitemap(sitemap_url);
+                self.result.add_sitemap(sitemap_url, line.get_span());
             }
             Err(error) => {
                 self.warnings.push(ParseWarning::parse_url(line, error));
@@ -219,6 +276,10 @@ impl Parser {
                 self.warnings.push(ParseWarning::wrong_clean_param_format(line));
                 return;
             }
+            if let Err(warning) = check_path_pattern(line, parts[0], self.options.get_limits()) {
+                self.warnings.push(warning);
+                return;
+            }
             clean_params_path_pattern = PathPattern::new(parts[0]);
             clean_params = *second_param;
         } else {
@@ -230,8 +291,9 @@ impl Parser {
             self.warnings
                 .push(ParseWarning::ignored_clean_params(line, invalid_clean_params));
         }
-        self.result
-            .add_clean_params(CleanParams::new(clean_params_path_pattern, valid_clean_params));
+        self.result.add_clean_params(
+            CleanParams::new(clean_params_path_pattern, valid_clean_params).with_span(line.get_span()),
+        );
     }
 
     fn parse_clean_params(clean_params: &str) -> (Vec<String>, Vec<String>) {
@@ -249,6 +311,7 @@ impl Parser {
         (valid, invalid)
     }
 
+    #[allow(clippy::almost_complete_range)]
     fn is_valid_clean_param(clean_param: &str) -> bool {
         for c in clean_param.chars() {
             let is_valid = ('A'..'Z').contains(&c)
@@ -265,6 +328,41 @@ impl Parser {
     }
 }
 
+/// Returns the path pattern of the `Allow` or `Disallow` directive.
+/// The lenient parser adds the leading `/` that is missing.
+fn get_path(value: &str, lenient: bool) -> Option<Cow<'_, str>> {
+    if value.starts_with('*') || value.starts_with('/') {
+        Some(Cow::Borrowed(value))
+    } else if lenient {
+        Some(Cow::Owned(format!("/{}", value)))
+    } else {
+        None
+    }
+}
+
+/// Checks that the path pattern is within the limits of the parser.
+fn check_path_pattern(line: &Line, path: &str, limits: &ParserLimits) -> Result<(), ParseWarning> {
+    if path.len() > limits.get_max_pattern_length() {
+        return Err(ParseWarning::pattern_too_long(line, limits.get_max_pattern_length()));
+    }
+    if path.matches('*').count() > limits.get_max_wildcards_per_pattern() {
+        return Err(ParseWarning::too_many_wildcards(
+            line,
+            limits.get_max_wildcards_per_pattern(),
+        ));
+    }
+    Ok(())
+}
+
+/// Adds the rule to the group unless the group already has the maximum number of rules.
+fn push_rule(group: &mut Group, line: &Line, rule: Rule, limits: &ParserLimits) -> Result<(), ParseWarning> {
+    if group.get_rules().len() >= limits.get_max_rules_per_group() {
+        return Err(ParseWarning::too_many_rules(line, limits.get_max_rules_per_group()));
+    }
+    group.push_rule(rule.with_span(line.get_span()));
+    Ok(())
+}
+
 fn ignore_bom(input: &str) -> &str {
     const BOM: &str = "\u{feff}";
     input.trim_start_matches(BOM)
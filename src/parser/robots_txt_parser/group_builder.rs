@@ -1,13 +1,33 @@
-use crate::model::{Group, RobotsTxt};
+use crate::model::{EvaluationMode, Group, RobotsTxt};
+use crate::parser::line::Line;
+use crate::parser::parser_limits::ParserLimits;
+use crate::parser::parser_options::ParserOptions;
+use crate::parser::warning::ParseWarning;
+use std::collections::HashMap;
 enum State {
     WaitingForNewGroup,
     WaitingForAdditionalUserAgent,
 }
 
+/// User agent line of the robots.txt file that started or extended a record.
+#[derive(Clone)]
+struct UserAgentLine {
+    user_agent: String,
+    line_no: usize,
+    line: String,
+}
+
+/// A group as it was written in the robots.txt file.
+/// Records are merged into groups when all records are processed.
+struct Record {
+    user_agents: Vec<UserAgentLine>,
+    group: Group,
+}
+
 pub struct GroupBuilder {
     state: State,
     active_group: Option<usize>,
-    groups: Vec<Group>,
+    records: Vec<Record>,
 }
 
 impl GroupBuilder {
@@ -15,40 +35,162 @@ impl GroupBuilder {
         GroupBuilder {
             state: State::WaitingForNewGroup,
             active_group: None,
-            groups: Vec::new(),
+            records: Vec::new(),
         }
     }
 
-    pub fn handle_user_agent(&mut self, user_agent: &str) {
+    pub fn handle_user_agent(&mut self, line: &Line, user_agent: &str) {
+        let user_agent_line = UserAgentLine {
+            user_agent: user_agent.to_lowercase(),
+            line_no: line.get_line_number(),
+            line: line.get_line_text().into(),
+        };
         match self.state {
             State::WaitingForNewGroup => {
                 let mut group = Group::new();
-                group.push_useragent(user_agent);
-                self.groups.push(group);
-                self.active_group = Some(self.groups.len() - 1);
+                group.extend_span(line.get_span());
+                self.records.push(Record {
+                    user_agents: vec![user_agent_line],
+                    group,
+                });
+                self.active_group = Some(self.records.len() - 1);
                 self.state = State::WaitingForAdditionalUserAgent;
             }
             State::WaitingForAdditionalUserAgent => {
-                let active_group = self.active_group.expect("Unable to get active group");
-                let group = self.groups.get_mut(active_group).expect("Unable to get group index");
-                if !group.contains_user_agent(user_agent) {
-                    group.push_useragent(user_agent);
+                let records = &mut self.records;
+                if let Some(record) = self.active_group.and_then(|active_group| records.get_mut(active_group)) {
+                    record.group.extend_span(line.get_span());
+                    if !record
+                        .user_agents
+                        .iter()
+                        .any(|item| item.user_agent == user_agent_line.user_agent)
+                    {
+                        record.user_agents.push(user_agent_line);
+                    }
                 }
             }
         }
     }
 
-    pub fn get_mut_active_group(&mut self) -> Option<&mut Group> {
+    /// Returns the group of the directive on the line, extending the group up to the line.
+    pub fn get_mut_active_group(&mut self, line: &Line) -> Option<&mut Group> {
         self.state = State::WaitingForNewGroup;
-        if let Some(active_group) = self.active_group {
-            return self.groups.get_mut(active_group);
-        }
-        None
+        let record = self.records.get_mut(self.active_group?)?;
+        record.group.extend_span(line.get_span());
+        Some(&mut record.group)
     }
 
-    pub fn fill_entries(mut self, robots_txt: &mut RobotsTxt) {
-        for group in self.groups.drain(..) {
+    /// Adds groups to the robots.txt model.
+    /// In the `EvaluationMode::Rfc9309` mode, records that share a user agent are merged into a single group
+    /// for that user agent as RFC 9309 requires. Otherwise every record is a group of its own,
+    /// and the first group of the user agent applies.
+    /// Groups beyond the maximum number of groups, and rules beyond the maximum number of rules
+    /// per group or in total, are dropped.
+    /// Returns warnings about merged records, dropped groups and dropped rules.
+    pub fn fill_entries(self, input: &str, robots_txt: &mut RobotsTxt, options: &ParserOptions) -> Vec<ParseWarning> {
+        let limits = options.get_limits();
+        let mut warnings = Vec::new();
+        let groups = match options.get_evaluation_mode() {
+            EvaluationMode::Legacy => self.records.into_iter().map(Record::into_group).collect(),
+            EvaluationMode::Rfc9309 => self.merge_records(input, limits, &mut warnings),
+        };
+        let mut rule_count = 0usize;
+        let mut rules_dropped = false;
+        for (group_index, (user_agent_line, mut group)) in groups.into_iter().enumerate() {
+            if group_index >= limits.get_max_groups() {
+                let line = Line::new(&user_agent_line.line, user_agent_line.line_no);
+                warnings.push(ParseWarning::too_many_groups(&line, limits.get_max_groups()));
+                break;
+            }
+            let max_rules = limits.get_max_rules().saturating_sub(rule_count);
+            if group.get_rules().len() > max_rules {
+                // Only the first dropped rule is reported, the following rules are dropped as well.
+                if let (false, Some(span)) = (rules_dropped, group.get_rules()[max_rules].get_span()) {
+                    let line = Line::new(&input[span.get_range()], span.get_line_no());
+                    warnings.push(ParseWarning::too_many_rules_in_total(&line, limits.get_max_rules()));
+                }
+                rules_dropped = true;
+                group.truncate_rules(max_rules);
+            }
+            rule_count += group.get_rules().len();
             robots_txt.add_group(group);
         }
+        warnings
+    }
+
+    /// Merges the records that share a user agent into groups in order of appearance of the user agents.
+    /// Returns the groups with the first user agent line of each group.
+    fn merge_records(
+        &self,
+        input: &str,
+        limits: &ParserLimits,
+        warnings: &mut Vec<ParseWarning>,
+    ) -> Vec<(UserAgentLine, Group)> {
+        // Indexes of the records of each user agent in order of appearance.
+        let mut user_agent_records: Vec<(&UserAgentLine, Vec<usize>)> = Vec::new();
+        let mut user_agent_indexes: HashMap<&str, usize> = HashMap::new();
+        for (record_index, record) in self.records.iter().enumerate() {
+            for user_agent_line in record.user_agents.iter() {
+                let user_agent = user_agent_line.user_agent.as_str();
+                match user_agent_indexes.get(user_agent) {
+                    Some(index) => {
+                        let (first_line, record_indexes) = &mut user_agent_records[*index];
+                        let line = Line::new(&user_agent_line.line, user_agent_line.line_no);
+                        warnings.push(ParseWarning::group_merged(&line, user_agent.into(), first_line.line_no));
+                        record_indexes.push(record_index);
+                    }
+                    None => {
+                        let _ = user_agent_indexes.insert(user_agent, user_agent_records.len());
+                        user_agent_records.push((user_agent_line, vec![record_index]));
+                    }
+                }
+            }
+        }
+        // User agents that are listed in the same records keep sharing one group.
+        let mut groups: Vec<(UserAgentLine, Group)> = Vec::new();
+        let mut group_indexes: HashMap<&[usize], usize> = HashMap::new();
+        for (user_agent_line, record_indexes) in user_agent_records.iter() {
+            match group_indexes.get(record_indexes.as_slice()) {
+                Some(index) => {
+                    groups[*index].1.push_useragent(&user_agent_line.user_agent);
+                }
+                None if groups.len() >= limits.get_max_groups() => {
+                    // The rules are not copied into the groups beyond the limit, which are dropped anyway.
+                    groups.push(((*user_agent_line).clone(), Group::new()));
+                    break;
+                }
+                None => {
+                    let mut group = Group::new();
+                    group.push_useragent(&user_agent_line.user_agent);
+                    for record_index in record_indexes.iter() {
+                        group.merge(&self.records[*record_index].group);
+                    }
+                    let max_rules = limits.get_max_rules_per_group();
+                    if group.get_rules().len() > max_rules {
+                        // The records are within the limit, so the first dropped rule comes from a merged record.
+                        if let Some(span) = group.get_rules()[max_rules].get_span() {
+                            let line = Line::new(&input[span.get_range()], span.get_line_no());
+                            warnings.push(ParseWarning::too_many_rules(&line, max_rules));
+                        }
+                        group.truncate_rules(max_rules);
+                    }
+                    let _ = group_indexes.insert(record_indexes.as_slice(), groups.len());
+                    groups.push(((*user_agent_line).clone(), group));
+                }
+            }
+        }
+        groups
+    }
+}
+
+impl Record {
+    /// Returns the group of the record with all its user agents and the first user agent line of the record.
+    fn into_group(self) -> (UserAgentLine, Group) {
+        let mut group = self.group;
+        for user_agent_line in self.user_agents.iter() {
+            group.push_useragent(&user_agent_line.user_agent);
+        }
+        let first_line = self.user_agents.into_iter().next().expect("Record without user agent");
+        (first_line, group)
     }
 }
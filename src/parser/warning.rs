@@ -119,6 +119,73 @@ impl ParseWarning {
         }
     }
 
+    pub(crate) fn group_merged(line: &Line, user_agent: String, first_line_no: usize) -> ParseWarning {
+        ParseWarning {
+            line_no: line.get_line_number(),
+            line: line.get_line_text().into(),
+            reason: WarningReason::GroupMerged {
+                user_agent,
+                first_line_no,
+            },
+        }
+    }
+
+    pub(crate) fn body_truncated(line: &Line, max_size: usize) -> ParseWarning {
+        ParseWarning {
+            line_no: line.get_line_number(),
+            line: line.get_line_text().into(),
+            reason: WarningReason::BodyTruncated { max_size },
+        }
+    }
+
+    pub(crate) fn too_many_lines(line: &Line, max_lines: usize) -> ParseWarning {
+        ParseWarning {
+            line_no: line.get_line_number(),
+            line: line.get_line_text().into(),
+            reason: WarningReason::TooManyLines { max_lines },
+        }
+    }
+
+    pub(crate) fn too_many_rules(line: &Line, max_rules: usize) -> ParseWarning {
+        ParseWarning {
+            line_no: line.get_line_number(),
+            line: line.get_line_text().into(),
+            reason: WarningReason::TooManyRules { max_rules },
+        }
+    }
+
+    pub(crate) fn too_many_groups(line: &Line, max_groups: usize) -> ParseWarning {
+        ParseWarning {
+            line_no: line.get_line_number(),
+            line: line.get_line_text().into(),
+            reason: WarningReason::TooManyGroups { max_groups },
+        }
+    }
+
+    pub(crate) fn too_many_rules_in_total(line: &Line, max_rules: usize) -> ParseWarning {
+        ParseWarning {
+            line_no: line.get_line_number(),
+            line: line.get_line_text().into(),
+            reason: WarningReason::TooManyRulesInTotal { max_rules },
+        }
+    }
+
+    pub(crate) fn pattern_too_long(line: &Line, max_length: usize) -> ParseWarning {
+        ParseWarning {
+            line_no: line.get_line_number(),
+            line: line.get_line_text().into(),
+            reason: WarningReason::PatternTooLong { max_length },
+        }
+    }
+
+    pub(crate) fn too_many_wildcards(line: &Line, max_wildcards: usize) -> ParseWarning {
+        ParseWarning {
+            line_no: line.get_line_number(),
+            line: line.get_line_text().into(),
+            reason: WarningReason::TooManyWildcards { max_wildcards },
+        }
+    }
+
     pub(crate) fn ignored_clean_params(line: &Line, ignored_clean_params: Vec<String>) -> ParseWarning {
         ParseWarning {
             line_no: line.get_line_number(),
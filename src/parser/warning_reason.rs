@@ -33,6 +33,26 @@ pub enum WarningReason {
     IgnoredCleanParams(Vec<String>),
     /// Error in URL path format.
     WrongPathFormat,
+    /// The group of this `User-Agent` directive was merged with the group of the same user agent
+    /// that was declared earlier at the line `first_line_no`.
+    GroupMerged { user_agent: String, first_line_no: usize },
+    /// The robots.txt file exceeds the maximum size of `max_size` bytes.
+    /// The text was cut off at the end of the last complete line before this size, and the rest was ignored.
+    BodyTruncated { max_size: usize },
+    /// The robots.txt file has more than `max_lines` lines. This line and the following lines were ignored.
+    TooManyLines { max_lines: usize },
+    /// The group already has `max_rules` rules, so the rule of this line was ignored.
+    TooManyRules { max_rules: usize },
+    /// The robots.txt file already has `max_groups` groups, so the group of this `User-Agent` directive
+    /// and the following groups were ignored.
+    TooManyGroups { max_groups: usize },
+    /// The groups of the robots.txt file already have `max_rules` rules in total,
+    /// so the rule of this line and the following rules were ignored.
+    TooManyRulesInTotal { max_rules: usize },
+    /// The path pattern is longer than `max_length` bytes, so it was ignored.
+    PatternTooLong { max_length: usize },
+    /// The path pattern has more than `max_wildcards` wildcards, so it was ignored.
+    TooManyWildcards { max_wildcards: usize },
 }
 
 /// Displays text of warning reason.
@@ -82,6 +102,53 @@ impl fmt::Display for WarningReason {
             Self::WrongPathFormat => {
                 write!(f, "Error in URL path format.")
             }
+            Self::GroupMerged {
+                user_agent,
+                first_line_no,
+            } => {
+                write!(
+                    f,
+                    "Group of user agent `{}` was merged with the group declared at line {}.",
+                    user_agent, first_line_no
+                )
+            }
+            Self::BodyTruncated { max_size } => {
+                write!(
+                    f,
+                    "The robots.txt file exceeds {} bytes. The rest of the file was ignored.",
+                    max_size
+                )
+            }
+            Self::TooManyLines { max_lines } => {
+                write!(
+                    f,
+                    "The robots.txt file has more than {} lines. The rest of the file was ignored.",
+                    max_lines
+                )
+            }
+            Self::TooManyRules { max_rules } => {
+                write!(f, "The group already has {} rules. The rule was ignored.", max_rules)
+            }
+            Self::TooManyGroups { max_groups } => {
+                write!(
+                    f,
+                    "The robots.txt file already has {} groups. The rest of the groups were ignored.",
+                    max_groups
+                )
+            }
+            Self::TooManyRulesInTotal { max_rules } => {
+                write!(
+                    f,
+                    "The groups already have {} rules in total. The rest of the rules were ignored.",
+                    max_rules
+                )
+            }
+            Self::PatternTooLong { max_length } => {
+                write!(f, "The path pattern is longer than {} bytes.", max_length)
+            }
+            Self::TooManyWildcards { max_wildcards } => {
+                write!(f, "The path pattern has more than {} wildcards.", max_wildcards)
+            }
         }
     }
 }
@@ -1,24 +1,50 @@
 mod fetched_robots_txt;
 mod robots_txt;
-use crate::model::RequestRate;
+use crate::model::{Decision, DecisionReason, Error, Path, RequestRate};
 use std::time::Duration;
 use url::Url;
 
 /// Trait that implements robots txt service.
+///
+/// The `user_agent` arguments accept either a bare product token like `Googlebot`
+/// or a full `User-Agent` header value like `Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)`.
 pub trait RobotsTxtService {
     /// Using the parsed robots.txt decide if useragent can fetch url.
     fn can_fetch(&self, user_agent: &str, url: &Url) -> bool;
 
+    /// Explains the result of `can_fetch`: returns the group and the rule that decided,
+    /// or another reason like the status of the fetched robots.txt file.
+    /// The default implementation returns only the result of `can_fetch` with `DecisionReason::Unexplained`.
+    fn explain(&self, user_agent: &str, url: &Url) -> Decision {
+        Decision::new(self.can_fetch(user_agent, url), DecisionReason::Unexplained)
+    }
+
+    /// Same as `can_fetch`, but returns the error for the url that has no path
+    /// that can be checked against the rules, like `mailto:` and `data:` urls.
+    fn try_can_fetch(&self, user_agent: &str, url: &Url) -> Result<bool, Error> {
+        Path::try_from_url(url)?;
+        Ok(self.can_fetch(user_agent, url))
+    }
+
     /// Returns the crawl delay for this user agent as a Duration, or None if no crawl delay is defined.
     fn get_crawl_delay(&self, user_agent: &str) -> Option<Duration>;
 
     /// Removes the request parameters from the url that were listed in the `Clean-param` directive.
+    /// The url is converted to the canonical form that is used to check the rules, see `robotparser::model::canonicalize_url`.
     /// This method CHECKS that the origin of the transmitted url matches the origin of robots.txt.
     /// Returns true if the operation was applied to the passed url.
     /// In other cases it returns false.
     fn normalize_url(&self, url: &mut Url) -> bool;
 
+    /// Same as `normalize_url`, but returns the error for the url that has no path
+    /// that can be checked against the rules, like `mailto:` and `data:` urls.
+    fn try_normalize_url(&self, url: &mut Url) -> Result<bool, Error> {
+        Path::try_from_url(url)?;
+        Ok(self.normalize_url(url))
+    }
+
     /// Removes the request parameters from the url that were listed in the `Clean-param` directive.
+    /// The url is converted to the canonical form that is used to check the rules, see `robotparser::model::canonicalize_url`.
     /// This method DOES NOT CHECK that the origin of the transmitted url coincides with the origin of robots.txt.
     fn normalize_url_ignore_origin(&self, url: &mut Url);
 
@@ -1,47 +1,97 @@
+use crate::model::canonicalize_url;
 use crate::model::RequestRate;
+use crate::model::{Clock, SystemClock};
+use crate::model::{Decision, DecisionReason, FetchStatus};
 use crate::model::{FetchedRobotsTxt, FetchedRobotsTxtContainer};
 use crate::service::RobotsTxtService;
 use std::time::Duration;
 use url::Url;
 
-impl RobotsTxtService for FetchedRobotsTxt {
-    fn can_fetch(&self, user_agent: &str, url: &Url) -> bool {
+impl FetchedRobotsTxt {
+    /// Checks that the user agent can fetch the url at the current time of the clock.
+    /// The clock decides whether the unreachable timeout elapsed, see `RobotsTxtService::can_fetch`.
+    pub fn can_fetch_at(&self, user_agent: &str, url: &Url, clock: &dyn Clock) -> bool {
         match *self.get_container() {
             FetchedRobotsTxtContainer::FetchDenied => false,
             FetchedRobotsTxtContainer::FetchFailed => true,
             FetchedRobotsTxtContainer::Fetched(ref robots_txt) => robots_txt.can_fetch(user_agent, url),
+            FetchedRobotsTxtContainer::Unreachable { .. } => {
+                if !self.is_unreachable_timeout_elapsed(clock) {
+                    // Complete disallow during the outage.
+                    return false;
+                }
+                match self.get_effective_robots_txt(clock) {
+                    Some(robots_txt) => robots_txt.can_fetch(user_agent, url),
+                    None => true,
+                }
+            }
         }
     }
 
+    /// Explains the result of `FetchedRobotsTxt::can_fetch_at`.
+    pub fn explain_at(&self, user_agent: &str, url: &Url, clock: &dyn Clock) -> Decision {
+        match *self.get_container() {
+            FetchedRobotsTxtContainer::FetchDenied => {
+                Decision::new(false, DecisionReason::FetchStatus(FetchStatus::Denied))
+            }
+            FetchedRobotsTxtContainer::FetchFailed => {
+                Decision::new(true, DecisionReason::FetchStatus(FetchStatus::Failed))
+            }
+            FetchedRobotsTxtContainer::Fetched(ref robots_txt) => robots_txt.explain(user_agent, url),
+            FetchedRobotsTxtContainer::Unreachable { .. } => {
+                if !self.is_unreachable_timeout_elapsed(clock) {
+                    return Decision::new(false, DecisionReason::FetchStatus(FetchStatus::Unreachable));
+                }
+                match self.get_effective_robots_txt(clock) {
+                    Some(robots_txt) => robots_txt.explain(user_agent, url),
+                    None => Decision::new(true, DecisionReason::FetchStatus(FetchStatus::Unreachable)),
+                }
+            }
+        }
+    }
+}
+
+impl RobotsTxtService for FetchedRobotsTxt {
+    fn can_fetch(&self, user_agent: &str, url: &Url) -> bool {
+        self.can_fetch_at(user_agent, url, &SystemClock)
+    }
+
+    fn explain(&self, user_agent: &str, url: &Url) -> Decision {
+        self.explain_at(user_agent, url, &SystemClock)
+    }
+
     fn get_crawl_delay(&self, user_agent: &str) -> Option<Duration> {
-        if let FetchedRobotsTxtContainer::Fetched(ref robots_txt) = *self.get_container() {
+        if let Some(robots_txt) = self.get_effective_robots_txt(&SystemClock) {
             return robots_txt.get_crawl_delay(user_agent);
         }
         None
     }
 
     fn normalize_url(&self, url: &mut Url) -> bool {
-        if let FetchedRobotsTxtContainer::Fetched(ref robots_txt) = *self.get_container() {
+        if let Some(robots_txt) = self.get_effective_robots_txt(&SystemClock) {
             return robots_txt.normalize_url(url);
         }
+        canonicalize_url(url);
         true
     }
 
     fn normalize_url_ignore_origin(&self, url: &mut Url) {
-        if let FetchedRobotsTxtContainer::Fetched(ref robots_txt) = *self.get_container() {
+        if let Some(robots_txt) = self.get_effective_robots_txt(&SystemClock) {
             robots_txt.normalize_url_ignore_origin(url);
+        } else {
+            canonicalize_url(url);
         }
     }
 
     fn get_sitemaps(&self) -> &[Url] {
-        if let FetchedRobotsTxtContainer::Fetched(ref robots_txt) = *self.get_container() {
+        if let Some(robots_txt) = self.get_effective_robots_txt(&SystemClock) {
             return robots_txt.get_sitemaps();
         }
         &[]
     }
 
     fn get_req_rate(&self, user_agent: &str) -> Option<RequestRate> {
-        if let FetchedRobotsTxtContainer::Fetched(ref robots_txt) = *self.get_container() {
+        if let Some(robots_txt) = self.get_effective_robots_txt(&SystemClock) {
             return robots_txt.get_req_rate(user_agent);
         }
         None
@@ -1,30 +1,46 @@
+use crate::model::canonicalize_url;
+use crate::model::EvaluationMode;
+use crate::model::Group;
 use crate::model::Path;
 use crate::model::RequestRate;
 use crate::model::RobotsTxt;
+use crate::model::RuleMatch;
+use crate::model::{Decision, DecisionReason};
 use crate::service::RobotsTxtService;
 use std::time::Duration;
-use url::Url;
+use url::{Host, Origin, Url};
 
 impl RobotsTxtService for RobotsTxt {
     fn can_fetch(&self, user_agent: &str, url: &Url) -> bool {
-        if url.origin() != *self.get_origin() {
+        if !is_same_origin(url, self.get_origin()) {
             return false;
         }
         let path = Path::from_url(url);
-        let rule_decision = self.find_in_group(user_agent, |group| {
-            let rules = group.get_rules_sorted_by_path_len_desc();
-            for rule in rules.iter() {
-                if rule.applies_to(&path) {
-                    return Some(rule.get_allowance());
-                }
-            }
-            None
-        });
-        if let Some(rule_decision) = rule_decision {
-            return rule_decision;
+        if is_robots_txt_path(self, &path) {
+            return true;
         }
         // Empty robots.txt allows crawling. Everything that was not denied must be allowed.
-        true
+        find_deciding_rule(self, user_agent, &path)
+            .map(|(_, rule_match)| rule_match.allowance)
+            .unwrap_or(true)
+    }
+
+    fn explain(&self, user_agent: &str, url: &Url) -> Decision {
+        if !is_same_origin(url, self.get_origin()) {
+            return Decision::new(false, DecisionReason::OriginMismatch);
+        }
+        let path = Path::from_url(url);
+        if is_robots_txt_path(self, &path) {
+            return Decision::new(true, DecisionReason::RobotsTxtPath);
+        }
+        match find_deciding_rule(self, user_agent, &path) {
+            Some((group, rule_match)) => Decision::from_rule(group, &group.get_rules()[rule_match.index]),
+            // The first group that applies to the user agent is named, although none of its rules matched.
+            None => match self.find_in_group(user_agent, Some) {
+                Some(group) => Decision::from_group(true, DecisionReason::NoMatchingRule, group),
+                None => Decision::new(true, DecisionReason::NoMatchingRule),
+            },
+        }
     }
 
     fn get_crawl_delay(&self, user_agent: &str) -> Option<Duration> {
@@ -32,7 +48,7 @@ impl RobotsTxtService for RobotsTxt {
     }
 
     fn normalize_url(&self, url: &mut Url) -> bool {
-        if url.origin() != *self.get_origin() {
+        if !is_same_origin(url, self.get_origin()) {
             return false;
         }
         self.normalize_url_ignore_origin(url);
@@ -40,7 +56,9 @@ impl RobotsTxtService for RobotsTxt {
     }
 
     fn normalize_url_ignore_origin(&self, url: &mut Url) {
-        if url.query().is_none() {
+        canonicalize_url(url);
+        // The empty query of the canonical URL is kept, since the rules match it.
+        if matches!(url.query(), None | Some("")) {
             return;
         }
         let mut query_params_to_filter = Vec::new();
@@ -63,6 +81,7 @@ impl RobotsTxtService for RobotsTxt {
                 }
             }
         }
+        // The URL whose parameters were all removed is the same as the URL without the query.
         if url.query() == Some("") {
             url.set_query(None);
         }
@@ -76,3 +95,53 @@ impl RobotsTxtService for RobotsTxt {
         self.find_in_group(user_agent, |group| group.get_req_rate())
     }
 }
+
+/// Checks that the url has the origin of the robots.txt file.
+/// The origin of the url is not built for the common schemes, because it owns the scheme and the host.
+fn is_same_origin(url: &Url, origin: &Origin) -> bool {
+    match *origin {
+        Origin::Tuple(ref scheme, ref host, port) if url.scheme() != "blob" => {
+            url.scheme() == scheme
+                && url.port_or_known_default() == Some(port)
+                && match (url.host(), host) {
+                    (Some(Host::Domain(url_host)), Host::Domain(host)) => url_host == host,
+                    (Some(Host::Ipv4(url_host)), Host::Ipv4(host)) => url_host == *host,
+                    (Some(Host::Ipv6(url_host)), Host::Ipv6(host)) => url_host == *host,
+                    _ => false,
+                }
+        }
+        _ => url.origin() == *origin,
+    }
+}
+
+const ROBOTS_TXT_PATH: &str = "/robots.txt";
+
+/// Checks that the path is the path of the robots.txt file, which is always allowed in the RFC 9309 mode.
+fn is_robots_txt_path(robots_txt: &RobotsTxt, path: &Path) -> bool {
+    robots_txt.get_evaluation_mode() == EvaluationMode::Rfc9309 && path.as_str() == ROBOTS_TXT_PATH
+}
+
+/// Returns the rule that decides whether the path can be fetched, along with its group.
+fn find_deciding_rule<'a>(robots_txt: &'a RobotsTxt, user_agent: &str, path: &Path) -> Option<(&'a Group, RuleMatch)> {
+    if robots_txt.get_evaluation_mode() == EvaluationMode::Legacy {
+        return robots_txt.find_in_group(user_agent, |group| {
+            group
+                .find_rule(path, EvaluationMode::Legacy)
+                .map(|rule_match| (group, rule_match))
+        });
+    }
+    let mut decision: Option<(&Group, RuleMatch)> = None;
+    robots_txt.for_each_rfc9309_group(user_agent, |group| {
+        if let Some(rule_match) = group.find_rule(path, EvaluationMode::Rfc9309) {
+            // The longest match wins. `Allow` wins when the length is the same.
+            let is_better = match decision {
+                Some((_, ref decision)) => rule_match.is_better_than(decision, EvaluationMode::Rfc9309),
+                None => true,
+            };
+            if is_better {
+                decision = Some((group, rule_match));
+            }
+        }
+    });
+    decision
+}
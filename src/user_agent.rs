@@ -0,0 +1,287 @@
+//! # User agent analysis
+//!
+//! Crawlers usually send a full `User-Agent` header, while robots.txt groups are declared for product tokens.
+//! This module extracts the product tokens of the crawler from the header value.
+//!
+//! # Example
+//! ```rust
+//! use robotparser::user_agent::extract_product_tokens;
+//!
+//! let user_agent = "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+//! assert_eq!(extract_product_tokens(user_agent), vec!["googlebot", "mozilla"]);
+//! assert_eq!(extract_product_tokens("Googlebot-Image/1.0"), vec!["googlebot-image"]);
+//! assert_eq!(extract_product_tokens("Googlebot"), vec!["googlebot"]);
+//! ```
+//!
+//! Crawlers of the same vendor often obey the group of a more general crawler when they have no group of their own.
+//! These fallback chains are described by `AgentFamilies`.
+//!
+//! ```rust
+//! use robotparser::model::{EvaluationMode, ProductTokenMatcher};
+//! use robotparser::parser::parse_robots_txt;
+//! use robotparser::service::RobotsTxtService;
+//! use robotparser::user_agent::AgentFamilies;
+//! use url::Url;
+//!
+//! let robots_txt_url = Url::parse("https://example.com/robots.txt").unwrap();
+//! let robots_txt = "User-agent: Googlebot\nDisallow: /private\n\nUser-agent: *\nDisallow: /";
+//! let mut robots_txt = parse_robots_txt(robots_txt_url.origin(), robots_txt).get_result();
+//! robots_txt.set_evaluation_mode(EvaluationMode::Rfc9309);
+//! robots_txt.set_user_agent_matcher(ProductTokenMatcher);
+//! robots_txt.set_agent_families(AgentFamilies::builtin());
+//! let url = Url::parse("https://example.com/public").unwrap();
+//! assert!(robots_txt.can_fetch("Googlebot-Image/1.0", &url));
+//! ```
+
+use std::collections::HashMap;
+
+/// Products of web browsers that are sent by crawlers for compatibility reasons.
+/// They are tried only after all other product tokens.
+const BROWSER_PRODUCTS: &[&str] = &[
+    "mozilla",
+    "applewebkit",
+    "khtml",
+    "gecko",
+    "chrome",
+    "chromium",
+    "safari",
+    "firefox",
+    "mobile",
+    "version",
+    "edg",
+    "opera",
+    "opr",
+    "trident",
+];
+
+/// Extracts the product tokens of the crawler from a bare product token or a full `User-Agent` header value.
+///
+/// Tokens are lowercased, stripped of version suffixes and ordered by priority:
+/// * product tokens found in comments, e.g. `Googlebot` in `(compatible; Googlebot/2.1)`;
+/// * product tokens outside of comments;
+/// * product tokens of web browsers, e.g. `Mozilla` or `Safari`.
+///
+/// When the user agent contains no valid product token, e.g. `foo.bot/1.0`, the lowercase name
+/// before the first `/` is returned, like the previous versions of this crate matched it.
+pub fn extract_product_tokens(user_agent: &str) -> Vec<String> {
+    let mut comment_tokens = Vec::new();
+    let mut product_tokens = Vec::new();
+    let mut comment_depth = 0usize;
+    let mut comment = String::new();
+    let mut product = String::new();
+    for c in user_agent.chars() {
+        match c {
+            '(' => {
+                if comment_depth == 0 {
+                    push_product_token(&mut product_tokens, &product);
+                    product.clear();
+                } else {
+                    comment.push(c);
+                }
+                comment_depth += 1;
+            }
+            ')' if comment_depth > 0 => {
+                comment_depth -= 1;
+                if comment_depth == 0 {
+                    push_comment_tokens(&mut comment_tokens, &comment);
+                    comment.clear();
+                } else {
+                    comment.push(c);
+                }
+            }
+            _ if comment_depth > 0 => {
+                comment.push(c);
+            }
+            _ if c.is_whitespace() => {
+                push_product_token(&mut product_tokens, &product);
+                product.clear();
+            }
+            _ => {
+                product.push(c);
+            }
+        }
+    }
+    if comment_depth > 0 {
+        push_comment_tokens(&mut comment_tokens, &comment);
+    }
+    push_product_token(&mut product_tokens, &product);
+
+    let (browser_tokens, product_tokens): (Vec<String>, Vec<String>) = product_tokens
+        .into_iter()
+        .partition(|token| BROWSER_PRODUCTS.contains(&token.as_str()));
+    let mut tokens: Vec<String> = Vec::new();
+    for token in comment_tokens.into_iter().chain(product_tokens).chain(browser_tokens) {
+        if !tokens.contains(&token) {
+            tokens.push(token);
+        }
+    }
+    if tokens.is_empty() {
+        let name = user_agent.split('/').next().unwrap_or("").trim();
+        if !name.is_empty() {
+            tokens.push(name.to_lowercase());
+        }
+    }
+    tokens
+}
+
+/// Returns the product token of the user agent that is a single lowercase product, e.g. `googlebot` or `googlebot/2.1`,
+/// without allocation. Returns `None` when the tokens must be extracted by `extract_product_tokens`.
+pub(crate) fn get_bare_product_token(user_agent: &str) -> Option<&str> {
+    if user_agent.contains(|c: char| c.is_whitespace() || c == '(' || c == ')') {
+        return None;
+    }
+    let token = user_agent.split('/').next().unwrap_or("");
+    if token == "*" || (is_product_token(token) && !token.bytes().any(|byte| byte.is_ascii_uppercase())) {
+        Some(token)
+    } else {
+        None
+    }
+}
+
+/// Adds the token of a product outside of comments, e.g. `Googlebot/2.1` or `*`.
+fn push_product_token(tokens: &mut Vec<String>, product: &str) {
+    let token = product.split('/').next().unwrap_or("").trim();
+    if token == "*" || is_product_token(token) {
+        tokens.push(token.to_lowercase());
+    }
+}
+
+/// Adds the tokens of products listed in a comment, e.g. `compatible; Googlebot/2.1; +http://www.google.com/bot.html`.
+/// Only items with a version are products, the other items are descriptions of the platform.
+fn push_comment_tokens(tokens: &mut Vec<String>, comment: &str) {
+    for item in comment.split(';') {
+        let item = item.trim();
+        if let Some(separator_index) = item.find('/') {
+            let token = &item[..separator_index];
+            if is_product_token(token) {
+                tokens.push(token.to_lowercase());
+            }
+        }
+    }
+}
+
+/// Checks that the text is a product token: `a-zA-Z0-9`, `_` and `-` characters, starting with a letter.
+fn is_product_token(token: &str) -> bool {
+    let mut chars = token.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() => chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-'),
+        _ => false,
+    }
+}
+
+/// Fallback chains of crawler families.
+///
+/// Each crawler is declared with an ordered list of product tokens whose groups it obeys
+/// when the robots.txt file has no group for the crawler itself.
+/// The `*` group is used only after all tokens of the chain.
+#[derive(Debug, Clone, Default)]
+pub struct AgentFamilies {
+    families: HashMap<String, Vec<String>>,
+}
+
+impl AgentFamilies {
+    /// Creates an empty set of crawler families.
+    pub fn new() -> AgentFamilies {
+        AgentFamilies::default()
+    }
+
+    /// Creates crawler families of all supported search engines.
+    pub fn builtin() -> AgentFamilies {
+        AgentFamilies::new()
+            .merge(AgentFamilies::google())
+            .merge(AgentFamilies::bing())
+            .merge(AgentFamilies::yandex())
+            .merge(AgentFamilies::baidu())
+    }
+
+    /// Creates crawler families of Google crawlers.
+    /// <https://developers.google.com/search/docs/crawling-indexing/overview-google-crawlers>
+    pub fn google() -> AgentFamilies {
+        AgentFamilies::new()
+            .with_family("Googlebot-Image", &["Googlebot"])
+            .with_family("Googlebot-News", &["Googlebot"])
+            .with_family("Googlebot-Video", &["Googlebot"])
+            .with_family("Storebot-Google", &["Googlebot"])
+            .with_family("Google-InspectionTool", &["Googlebot"])
+            .with_family("GoogleOther-Image", &["GoogleOther"])
+            .with_family("GoogleOther-Video", &["GoogleOther"])
+            .with_family("AdsBot-Google-Mobile", &["AdsBot-Google"])
+    }
+
+    /// Creates crawler families of Bing crawlers.
+    /// <https://www.bing.com/webmasters/help/which-crawlers-does-bing-use-8c184ec0>
+    pub fn bing() -> AgentFamilies {
+        AgentFamilies::new()
+            .with_family("bingbot", &["msnbot"])
+            .with_family("BingPreview", &["bingbot", "msnbot"])
+            .with_family("msnbot-media", &["msnbot"])
+            .with_family("adidxbot", &["bingbot", "msnbot"])
+    }
+
+    /// Creates crawler families of Yandex crawlers. All Yandex crawlers obey the `Yandex` group.
+    /// <https://yandex.com/support/webmaster/controlling-robot/robots-txt.html>
+    pub fn yandex() -> AgentFamilies {
+        AgentFamilies::new()
+            .with_family("YandexBot", &["Yandex"])
+            .with_family("YandexImages", &["Yandex"])
+            .with_family("YandexVideo", &["Yandex"])
+            .with_family("YandexMedia", &["Yandex"])
+            .with_family("YandexNews", &["Yandex"])
+            .with_family("YandexMobileBot", &["YandexBot", "Yandex"])
+    }
+
+    /// Creates crawler families of Baidu crawlers.
+    /// <https://help.baidu.com/question?prod_id=99&class=476&id=2996>
+    pub fn baidu() -> AgentFamilies {
+        AgentFamilies::new()
+            .with_family("Baiduspider-image", &["Baiduspider"])
+            .with_family("Baiduspider-video", &["Baiduspider"])
+            .with_family("Baiduspider-news", &["Baiduspider"])
+            .with_family("Baiduspider-favo", &["Baiduspider"])
+            .with_family("Baiduspider-cpro", &["Baiduspider"])
+            .with_family("Baiduspider-ads", &["Baiduspider"])
+    }
+
+    /// Declares the product tokens that the crawler obeys, in order, when it has no group of its own.
+    /// A previous declaration for the same crawler is replaced.
+    pub fn add_family(&mut self, product_token: &str, fallback_tokens: &[&str]) {
+        let fallback_tokens = fallback_tokens.iter().map(|token| token.to_lowercase()).collect();
+        self.families.insert(product_token.to_lowercase(), fallback_tokens);
+    }
+
+    /// Declares the product tokens that the crawler obeys, in order, when it has no group of its own.
+    pub fn with_family(mut self, product_token: &str, fallback_tokens: &[&str]) -> AgentFamilies {
+        self.add_family(product_token, fallback_tokens);
+        self
+    }
+
+    /// Adds the crawler families of other set to this set.
+    pub fn merge(mut self, other: AgentFamilies) -> AgentFamilies {
+        self.families.extend(other.families);
+        self
+    }
+
+    /// Returns the fallback tokens of the crawler, not including the crawler itself.
+    pub fn get_fallback_tokens(&self, product_token: &str) -> &[String] {
+        let tokens = if product_token.chars().any(char::is_uppercase) {
+            self.families.get(&product_token.to_lowercase())
+        } else {
+            self.families.get(product_token)
+        };
+        tokens.map(|tokens| tokens.as_slice()).unwrap_or(&[])
+    }
+
+    /// Returns the product tokens of the user agent, each followed by its fallback tokens.
+    pub fn expand(&self, product_tokens: &[String]) -> Vec<String> {
+        let mut tokens: Vec<String> = Vec::new();
+        for product_token in product_tokens.iter() {
+            let chain = std::iter::once(product_token).chain(self.get_fallback_tokens(product_token).iter());
+            for token in chain {
+                if !tokens.contains(token) {
+                    tokens.push(token.clone());
+                }
+            }
+        }
+        tokens
+    }
+}
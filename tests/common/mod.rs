@@ -0,0 +1,119 @@
+#![allow(dead_code)]
+
+use regex::Regex;
+use robotparser::model::{FetchPolicy, RetryPolicy};
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use url::{Origin, Url};
+
+/// Returns the origin of the robots.txt file that is parsed by the tests.
+pub fn origin() -> Origin {
+    Url::parse("https://example.com/robots.txt").unwrap().origin()
+}
+
+/// Returns the RFC 9309 policy without retries, so that failed fetches end immediately.
+pub fn policy() -> FetchPolicy {
+    FetchPolicy::rfc9309().with_retry_policy(RetryPolicy::none())
+}
+
+/// Returns the reference implementation of the path pattern: `*` matches any sequence of characters,
+/// `$` matches the end of the path, and the pattern matches any path with the matching prefix.
+pub fn reference(pattern: &str) -> Regex {
+    let mut regex = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '$' => regex.push('$'),
+            _ => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    Regex::new(&regex).unwrap()
+}
+
+/// HTTP response that is served by the test server.
+#[derive(Clone)]
+pub struct TestResponse {
+    pub status_code: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl TestResponse {
+    pub fn new(status_code: u16, body: &str) -> TestResponse {
+        TestResponse {
+            status_code,
+            headers: Vec::new(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> TestResponse {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// Minimal HTTP server that answers requests with the passed responses in order.
+/// The last response is repeated when all responses are served.
+pub struct TestServer {
+    url: Url,
+    requests: Arc<Mutex<Vec<Vec<String>>>>,
+}
+
+impl TestServer {
+    pub fn start(responses: Vec<TestResponse>) -> TestServer {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let server_requests = requests.clone();
+        thread::spawn(move || {
+            for (index, stream) in listener.incoming().enumerate() {
+                let mut stream = match stream {
+                    Ok(stream) => stream,
+                    Err(_) => return,
+                };
+                let mut request = Vec::new();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap_or(0) == 0 {
+                        break;
+                    }
+                    let line = line.trim_end().to_string();
+                    if line.is_empty() {
+                        break;
+                    }
+                    request.push(line);
+                }
+                server_requests.lock().unwrap().push(request);
+                let response = &responses[index.min(responses.len() - 1)];
+                let mut head = format!("HTTP/1.1 {} Test\r\n", response.status_code);
+                for (name, value) in response.headers.iter() {
+                    head.push_str(&format!("{}: {}\r\n", name, value));
+                }
+                head.push_str(&format!(
+                    "Content-Length: {}\r\nConnection: close\r\n\r\n",
+                    response.body.len()
+                ));
+                let _ = stream.write_all(head.as_bytes());
+                let _ = stream.write_all(&response.body);
+            }
+        });
+        TestServer { url, requests }
+    }
+
+    pub fn origin(&self) -> Origin {
+        self.url.origin()
+    }
+
+    pub fn url(&self, path: &str) -> Url {
+        self.url.join(path).unwrap()
+    }
+
+    /// Returns the request lines and headers of all received requests.
+    pub fn requests(&self) -> Vec<Vec<String>> {
+        self.requests.lock().unwrap().clone()
+    }
+}
@@ -0,0 +1,88 @@
+use robotparser::model::{EvaluationMode, RobotsTxt};
+use robotparser::parser::parse_robots_txt;
+use robotparser::service::RobotsTxtService;
+use robotparser::user_agent::AgentFamilies;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use url::Url;
+
+/// Allocator that counts the allocations of the current thread, so that parallel tests do not interfere.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn count_allocations(action: impl FnOnce() -> bool) -> usize {
+    let before = ALLOCATIONS.with(|allocations| allocations.get());
+    std::hint::black_box(action());
+    ALLOCATIONS.with(|allocations| allocations.get()) - before
+}
+
+const DOC: &str = "\n\
+User-agent: *\n\
+Disallow: /private\n\
+\n\
+User-agent: googlebot\n\
+Allow: /private/public\n\
+Disallow: /*.pdf$\n\
+Disallow: /search?\n\
+";
+
+fn parse(evaluation_mode: EvaluationMode) -> RobotsTxt {
+    let url = Url::parse("https://www.example.com/robots.txt").unwrap();
+    let mut robots_txt = parse_robots_txt(url.origin(), DOC).get_result();
+    robots_txt.set_evaluation_mode(evaluation_mode);
+    robots_txt.set_agent_families(AgentFamilies::builtin());
+    robots_txt
+}
+
+#[test]
+fn test_can_fetch_does_not_allocate() {
+    let urls = [
+        "https://www.example.com/private/public/page.html",
+        "https://www.example.com/private/file.pdf",
+        "https://www.example.com/search?q=robots",
+        "https://www.example.com/~user/a%2Fb",
+        "https://www.example.com:443/",
+        "https://other.example.com/private",
+    ];
+    for evaluation_mode in [EvaluationMode::Legacy, EvaluationMode::Rfc9309] {
+        let robots_txt = parse(evaluation_mode);
+        for url in urls.iter() {
+            let url = Url::parse(url).unwrap();
+            for user_agent in ["googlebot", "googlebot/2.1", "googlebot-image", "bingbot", "*"] {
+                let allocations = count_allocations(|| robots_txt.can_fetch(user_agent, &url));
+                assert_eq!(allocations, 0, "{:?} {} {}", evaluation_mode, user_agent, url);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_can_fetch_allocates_for_unnormalized_input() {
+    let robots_txt = parse(EvaluationMode::Rfc9309);
+    let url = Url::parse("https://www.example.com/private").unwrap();
+    assert!(count_allocations(|| robots_txt.can_fetch("Googlebot/2.1 (+http://www.google.com/bot.html)", &url)) > 0);
+    let url = Url::parse("https://www.example.com/%7euser").unwrap();
+    assert!(count_allocations(|| robots_txt.can_fetch("googlebot", &url)) > 0);
+}
@@ -0,0 +1,97 @@
+mod common;
+
+use common::{TestResponse, TestServer};
+use robotparser::http::RobotsTxtClient;
+use robotparser::model::{FetchMetadata, FetchPolicy};
+use robotparser::parser::{parse_fetched_robots_txt_with_metadata, WarningReason};
+use robotparser::service::RobotsTxtService;
+use tokio::runtime::Runtime;
+use url::Url;
+
+const MAX_SIZE: usize = 1024;
+
+fn policy() -> FetchPolicy {
+    common::policy().with_max_body_size(MAX_SIZE)
+}
+
+/// Returns the robots.txt file whose `Disallow: /after` rule starts after the maximum size.
+fn large_doc() -> String {
+    let mut doc = String::from("User-agent: *\nDisallow: /before\n");
+    while doc.len() < MAX_SIZE - 10 {
+        doc.push_str("# padding\n");
+    }
+    doc.push_str("Disallow: /after\n");
+    doc.push_str(&"# padding\n".repeat(1000));
+    doc
+}
+
+#[test]
+fn test_default_max_body_size() {
+    assert_eq!(FetchPolicy::default().get_max_body_size(), 500 * 1024);
+    assert_eq!(FetchPolicy::rfc9309().get_max_body_size(), 500 * 1024);
+}
+
+#[test]
+fn test_body_truncated_by_parser() {
+    let origin = Url::parse("https://example.com/robots.txt").unwrap().origin();
+    let doc = large_doc();
+    let result = parse_fetched_robots_txt_with_metadata(origin.clone(), FetchMetadata::new(200), &doc, &policy());
+    assert_eq!(result.get_warnings().len(), 1);
+    let warning = &result.get_warnings()[0];
+    assert!(matches!(
+        warning.get_reason(),
+        WarningReason::BodyTruncated { max_size: MAX_SIZE }
+    ));
+    let before_cut = doc[..MAX_SIZE].lines().count();
+    assert_eq!(warning.get_line_no(), before_cut);
+    assert!("Disallow: /after".starts_with(warning.get_line_text().as_str()));
+    let robots_txt = result.get_result();
+    assert!(!robots_txt.can_fetch("*", &Url::parse("https://example.com/before").unwrap()));
+    assert!(robots_txt.can_fetch("*", &Url::parse("https://example.com/after").unwrap()));
+    let body = robots_txt.get_body().unwrap();
+    assert!(body.len() <= MAX_SIZE);
+    assert!(body.ends_with('\n'));
+    assert_eq!(robots_txt.get_metadata().get_body_size(), Some(body.len()));
+
+    let result = parse_fetched_robots_txt_with_metadata(origin, FetchMetadata::new(200), body, &policy());
+    assert!(result.get_warnings().is_empty());
+}
+
+#[test]
+fn test_body_truncated_at_char_boundary() {
+    let origin = Url::parse("https://example.com/robots.txt").unwrap().origin();
+    let doc = "User-agent: *\nDisallow: /ä\n";
+    let policy = policy().with_max_body_size(doc.len() - 3);
+    let result = parse_fetched_robots_txt_with_metadata(origin, FetchMetadata::new(200), doc, &policy);
+    let warning = &result.get_warnings()[0];
+    assert_eq!(warning.get_line_no(), 2);
+    assert_eq!(warning.get_line_text(), "Disallow: /");
+    assert_eq!(result.get_result().get_body(), Some("User-agent: *\n"));
+}
+
+#[test]
+fn test_body_truncated_by_client() {
+    let doc = large_doc();
+    let server = TestServer::start(vec![TestResponse::new(200, &doc)]);
+    let result = reqwest::blocking::Client::new()
+        .fetch_robots_txt_with_policy(server.origin(), &policy())
+        .unwrap();
+    assert_eq!(result.get_warnings().len(), 1);
+    let robots_txt = result.get_result();
+    assert!(!robots_txt.can_fetch("*", &server.url("/before")));
+    assert!(robots_txt.can_fetch("*", &server.url("/after")));
+    assert!(robots_txt.get_metadata().get_body_size().unwrap() <= MAX_SIZE);
+
+    let runtime = Runtime::new().unwrap();
+    let result = runtime
+        .block_on(
+            reqwest::Client::new()
+                .fetch_robots_txt_with_policy(server.origin(), &policy())
+                .unwrap(),
+        )
+        .unwrap();
+    assert_eq!(result.get_warnings().len(), 1);
+    let robots_txt = result.get_result();
+    assert!(!robots_txt.can_fetch("*", &server.url("/before")));
+    assert!(robots_txt.can_fetch("*", &server.url("/after")));
+}
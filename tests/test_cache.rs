@@ -0,0 +1,171 @@
+mod common;
+
+use common::{policy, TestResponse, TestServer};
+use robotparser::cache::{get_or_fetch, get_or_fetch_async, MemoryRobotsCache, RobotsCache};
+use robotparser::http::RobotsTxtClient;
+use robotparser::model::{Clock, FetchMetadata, FetchPolicy, FetchedRobotsTxt};
+use robotparser::parser::parse_fetched_robots_txt_with_metadata;
+use robotparser::service::RobotsTxtService;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use tokio::runtime::Runtime;
+use url::{Origin, Url};
+
+const DOC: &str = "User-agent: *\nDisallow: /private";
+
+/// Clock that is moved forward manually.
+#[derive(Clone, Default)]
+struct ManualClock(Arc<AtomicU64>);
+
+impl ManualClock {
+    fn advance(&self, duration: Duration) {
+        self.0.fetch_add(duration.as_secs(), Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(self.0.load(Ordering::SeqCst))
+    }
+}
+
+fn origin(host: &str) -> Origin {
+    Url::parse(&format!("https://{}/", host)).unwrap().origin()
+}
+
+fn robots_txt(clock: &ManualClock, headers: &[(&str, &str)]) -> Arc<FetchedRobotsTxt> {
+    let mut metadata = FetchMetadata::new(200).with_fetched_at(clock.now());
+    for (name, value) in headers {
+        metadata = metadata.with_header(name, value);
+    }
+    let result = parse_fetched_robots_txt_with_metadata(origin("example.com"), metadata, DOC, &FetchPolicy::default());
+    Arc::new(result.get_result())
+}
+
+#[test]
+fn test_cache_lookup_by_url() {
+    let clock = ManualClock::default();
+    let cache = MemoryRobotsCache::new(10).with_clock(clock.clone());
+    assert!(cache.is_empty());
+    cache.insert(origin("example.com"), robots_txt(&clock, &[]));
+    assert_eq!(cache.len(), 1);
+    let url = Url::parse("https://example.com/private/page?query=1").unwrap();
+    let cached = cache.get(&url).unwrap();
+    assert!(!cached.can_fetch("*", &url));
+    assert!(cache.get(&Url::parse("http://example.com/").unwrap()).is_none());
+    assert!(cache.get(&Url::parse("https://example.com:8443/").unwrap()).is_none());
+    assert!(cache.remove(&origin("example.com")).is_some());
+    assert!(cache.get(&url).is_none());
+}
+
+#[test]
+fn test_cache_lru_eviction() {
+    let clock = ManualClock::default();
+    let cache = MemoryRobotsCache::new(2).with_clock(clock.clone());
+    cache.insert(origin("a.com"), robots_txt(&clock, &[]));
+    cache.insert(origin("b.com"), robots_txt(&clock, &[]));
+    assert!(cache.get_by_origin(&origin("a.com")).is_some());
+    cache.insert(origin("c.com"), robots_txt(&clock, &[]));
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get_by_origin(&origin("a.com")).is_some());
+    assert!(cache.get_by_origin(&origin("b.com")).is_none());
+    assert!(cache.get_by_origin(&origin("c.com")).is_some());
+    cache.insert(origin("c.com"), robots_txt(&clock, &[]));
+    assert_eq!(cache.len(), 2);
+    cache.clear();
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_cache_ttl() {
+    let clock = ManualClock::default();
+    let cache = MemoryRobotsCache::new(10)
+        .with_ttl(Duration::from_secs(60))
+        .with_clock(clock.clone());
+    assert_eq!(cache.get_ttl(), Some(Duration::from_secs(60)));
+    cache.insert(origin("example.com"), robots_txt(&clock, &[]));
+    clock.advance(Duration::from_secs(59));
+    assert!(cache.get_by_origin(&origin("example.com")).is_some());
+    clock.advance(Duration::from_secs(1));
+    assert!(cache.get_by_origin(&origin("example.com")).is_none());
+    assert!(cache.is_empty());
+}
+
+#[test]
+fn test_cache_robots_txt_expiry() {
+    let clock = ManualClock::default();
+    let cache = MemoryRobotsCache::new(10)
+        .with_ttl(Duration::from_secs(3600))
+        .with_clock(clock.clone());
+    cache.insert(origin("a.com"), robots_txt(&clock, &[("Cache-Control", "max-age=30")]));
+    cache.insert(origin("b.com"), robots_txt(&clock, &[]));
+    clock.advance(Duration::from_secs(30));
+    cache.purge_expired();
+    assert_eq!(cache.len(), 1);
+    assert!(cache.get_by_origin(&origin("a.com")).is_none());
+    assert!(cache.get_by_origin(&origin("b.com")).is_some());
+}
+
+#[test]
+fn test_cache_shared_between_threads() {
+    let clock = ManualClock::default();
+    let cache = Arc::new(MemoryRobotsCache::new(100).with_clock(clock.clone()));
+    let threads: Vec<_> = (0..4)
+        .map(|index| {
+            let cache = cache.clone();
+            let clock = clock.clone();
+            thread::spawn(move || {
+                for item in 0..50 {
+                    let origin = origin(&format!("host{}.com", (index * 50 + item) % 120));
+                    cache.insert(origin.clone(), robots_txt(&clock, &[]));
+                    cache.get_by_origin(&origin);
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    assert_eq!(cache.len(), 100);
+}
+
+#[test]
+fn test_cache_get_or_fetch_blocking() {
+    let server = TestServer::start(vec![TestResponse::new(200, DOC)]);
+    let client = reqwest::blocking::Client::new();
+    let policy = policy();
+    let cache = MemoryRobotsCache::new(10);
+    for path in &["/", "/private", "/public"] {
+        let url = server.url(path);
+        let robots_txt = get_or_fetch(&cache, &url, |origin| {
+            client
+                .fetch_robots_txt_with_policy(origin, &policy)
+                .map(|result| result.get_result())
+        })
+        .unwrap();
+        assert_eq!(robots_txt.can_fetch("*", &url), *path != "/private");
+    }
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn test_cache_get_or_fetch_async() {
+    let server = TestServer::start(vec![TestResponse::new(200, DOC)]);
+    let client = reqwest::Client::new();
+    let policy = policy();
+    let cache: Arc<dyn RobotsCache> = Arc::new(MemoryRobotsCache::new(10));
+    let runtime = Runtime::new().unwrap();
+    for path in &["/", "/private"] {
+        let url = server.url(path);
+        let robots_txt = runtime
+            .block_on(get_or_fetch_async(&*cache, &url, |origin| async {
+                let result = client.fetch_robots_txt_with_policy(origin, &policy)?.await?;
+                Ok::<_, robotparser::model::Error>(result.get_result())
+            }))
+            .unwrap();
+        assert_eq!(robots_txt.can_fetch("*", &url), *path != "/private");
+    }
+    assert_eq!(server.requests().len(), 1);
+}
@@ -0,0 +1,163 @@
+mod common;
+
+use common::{origin, policy, TestResponse, TestServer};
+use robotparser::http::RobotsTxtClient;
+use robotparser::model::{Clock, FetchMetadata, FetchPolicy, FetchedRobotsTxt, SystemClock};
+use robotparser::parser::parse_fetched_robots_txt_with_metadata;
+use std::time::{Duration, SystemTime};
+use url::Url;
+
+const DOC: &str = "User-agent: *\nDisallow: /private";
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Clock that always returns the same time.
+struct FixedClock(SystemTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+/// 2015-10-21T07:28:00Z
+fn fetched_at() -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(1_445_412_480)
+}
+
+fn fetch(headers: &[(&str, &str)]) -> FetchedRobotsTxt {
+    fetch_with_policy(200, headers, &FetchPolicy::default())
+}
+
+fn fetch_unreachable(headers: &[(&str, &str)]) -> FetchedRobotsTxt {
+    fetch_with_policy(503, headers, &FetchPolicy::rfc9309())
+}
+
+fn fetch_with_policy(status_code: u16, headers: &[(&str, &str)], policy: &FetchPolicy) -> FetchedRobotsTxt {
+    let mut metadata = FetchMetadata::new(status_code).with_fetched_at(fetched_at());
+    for (name, value) in headers {
+        metadata = metadata.with_header(name, value);
+    }
+    parse_fetched_robots_txt_with_metadata(origin(), metadata, DOC, policy).get_result()
+}
+
+#[test]
+fn test_expiry_default_lifetime() {
+    let robots_txt = fetch(&[]);
+    assert_eq!(robots_txt.get_cache_lifetime(), DAY);
+    assert_eq!(robots_txt.expires_at(), fetched_at() + DAY);
+    let before = FixedClock(fetched_at() + DAY - Duration::from_secs(1));
+    assert!(!robots_txt.is_expired(&before));
+    let after = FixedClock(fetched_at() + DAY);
+    assert!(robots_txt.is_expired(&after));
+}
+
+#[test]
+fn test_expiry_max_age() {
+    let robots_txt = fetch(&[("Cache-Control", "public, max-age=3600")]);
+    assert_eq!(robots_txt.get_cache_lifetime(), Duration::from_secs(3600));
+    assert!(!robots_txt.is_expired(&FixedClock(fetched_at() + Duration::from_secs(3599))));
+    assert!(robots_txt.is_expired(&FixedClock(fetched_at() + Duration::from_secs(3600))));
+    let robots_txt = fetch(&[("Cache-Control", "max-age=3600"), ("Age", "600")]);
+    assert_eq!(robots_txt.get_cache_lifetime(), Duration::from_secs(3000));
+}
+
+#[test]
+fn test_expiry_unreachable_lifetime() {
+    let robots_txt = fetch_unreachable(&[]);
+    assert_eq!(robots_txt.get_cache_lifetime(), Duration::from_secs(300));
+    assert!(robots_txt.is_expired(&FixedClock(fetched_at() + Duration::from_secs(300))));
+    let robots_txt = fetch_unreachable(&[("Retry-After", "120")]);
+    assert_eq!(robots_txt.get_cache_lifetime(), Duration::from_secs(120));
+    let robots_txt = fetch_unreachable(&[("Retry-After", "Wed, 21 Oct 2015 07:38:00 GMT")]);
+    assert_eq!(robots_txt.get_cache_lifetime(), Duration::from_secs(600));
+    let robots_txt = fetch_unreachable(&[("Cache-Control", "max-age=60"), ("Retry-After", "120")]);
+    assert_eq!(robots_txt.get_cache_lifetime(), Duration::from_secs(60));
+}
+
+#[test]
+fn test_expiry_max_age_is_capped() {
+    let robots_txt = fetch(&[("Cache-Control", "max-age=31536000")]);
+    assert_eq!(robots_txt.get_cache_lifetime(), DAY);
+}
+
+#[test]
+fn test_expiry_no_cache() {
+    let robots_txt = fetch(&[("Cache-Control", "no-cache")]);
+    assert_eq!(robots_txt.get_cache_lifetime(), Duration::from_secs(0));
+    assert!(robots_txt.is_expired(&FixedClock(fetched_at())));
+    let robots_txt = fetch(&[("Cache-Control", "max-age=3600, no-store")]);
+    assert_eq!(robots_txt.get_cache_lifetime(), Duration::from_secs(0));
+}
+
+#[test]
+fn test_expiry_expires_header() {
+    let robots_txt = fetch(&[
+        ("Date", "Wed, 21 Oct 2015 07:28:00 GMT"),
+        ("Expires", "Wed, 21 Oct 2015 09:28:00 GMT"),
+    ]);
+    assert_eq!(robots_txt.get_cache_lifetime(), Duration::from_secs(2 * 60 * 60));
+    let robots_txt = fetch(&[("Expires", "Wed, 21 Oct 2015 08:28:00 GMT")]);
+    assert_eq!(robots_txt.get_cache_lifetime(), Duration::from_secs(60 * 60));
+    let robots_txt = fetch(&[("Expires", "0")]);
+    assert_eq!(robots_txt.get_cache_lifetime(), Duration::from_secs(0));
+}
+
+#[test]
+fn test_expiry_out_of_range_dates() {
+    let robots_txt = fetch(&[("Expires", "Sun, 06 Nov 999999999999999 08:49:37 GMT")]);
+    assert_eq!(robots_txt.get_cache_lifetime(), Duration::from_secs(0));
+    assert_eq!(robots_txt.expires_at(), fetched_at());
+    let robots_txt = fetch(&[
+        ("Date", "Sun, 06 Nov 999999999999999 08:49:37 GMT"),
+        ("Expires", "Wed, 21 Oct 2015 08:28:00 GMT"),
+    ]);
+    assert_eq!(robots_txt.get_cache_lifetime(), Duration::from_secs(60 * 60));
+
+    // The expiry time that is not representable does not overflow.
+    let last_second = SystemTime::UNIX_EPOCH + Duration::from_secs(i64::MAX as u64);
+    let metadata = FetchMetadata::new(200).with_fetched_at(last_second);
+    let robots_txt =
+        parse_fetched_robots_txt_with_metadata(origin(), metadata, DOC, &FetchPolicy::default()).get_result();
+    assert_eq!(robots_txt.expires_at(), last_second);
+    assert!(robots_txt.is_expired(&FixedClock(last_second)));
+}
+
+#[test]
+fn test_expiry_unreachable_timeout_uses_clock() {
+    let url = Url::parse("https://example.com/private").unwrap();
+    let policy = FetchPolicy::rfc9309().with_unreachable_timeout(DAY);
+    let robots_txt = fetch_with_policy(503, &[], &policy);
+    assert!(!robots_txt.is_unreachable_timeout_elapsed(&FixedClock(fetched_at() + DAY - Duration::from_secs(1))));
+    assert!(!robots_txt.can_fetch_at("foobot", &url, &FixedClock(fetched_at())));
+    assert!(robots_txt.is_unreachable_timeout_elapsed(&FixedClock(fetched_at() + DAY)));
+    assert!(robots_txt.can_fetch_at("foobot", &url, &FixedClock(fetched_at() + DAY)));
+}
+
+#[test]
+fn test_expiry_max_age_overrides_expires() {
+    let robots_txt = fetch(&[
+        ("Cache-Control", "max-age=60"),
+        ("Expires", "Wed, 21 Oct 2015 09:28:00 GMT"),
+    ]);
+    assert_eq!(robots_txt.get_cache_lifetime(), Duration::from_secs(60));
+}
+
+#[test]
+fn test_expiry_headers_from_client() {
+    let server = TestServer::start(vec![
+        TestResponse::new(200, DOC).with_header("Cache-Control", "max-age=120")
+    ]);
+    let client = reqwest::blocking::Client::new();
+    let policy = policy();
+    let robots_txt = client
+        .fetch_robots_txt_with_policy(server.origin(), &policy)
+        .unwrap()
+        .get_result();
+    assert_eq!(robots_txt.get_metadata().get_status_code(), Some(200));
+    assert_eq!(
+        robots_txt.get_metadata().get_header("cache-control"),
+        Some("max-age=120")
+    );
+    assert_eq!(robots_txt.get_cache_lifetime(), Duration::from_secs(120));
+    assert!(!robots_txt.is_expired(&SystemClock));
+}
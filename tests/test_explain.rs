@@ -0,0 +1,165 @@
+mod common;
+
+use common::origin;
+use robotparser::model::{DecisionReason, EvaluationMode, FetchMetadata, FetchPolicy, FetchStatus, RequestRate};
+use robotparser::parser::{parse_fetched_robots_txt, parse_fetched_robots_txt_with_metadata, parse_robots_txt};
+use robotparser::service::RobotsTxtService;
+use std::time::Duration;
+use url::Url;
+
+const DOC: &str = "User-agent: *\n\
+Disallow: /private\n\
+\n\
+User-agent: GoogleBot\n\
+User-agent: bingbot\n\
+Disallow: /search\n\
+Allow: /search/about\n\
+Disallow:\n";
+
+fn url(path: &str) -> Url {
+    Url::parse("https://example.com").unwrap().join(path).unwrap()
+}
+
+#[test]
+fn test_explain_rule() {
+    let robots_txt = parse_robots_txt(origin(), DOC).get_result();
+    let decision = robots_txt.explain("googlebot", &url("/search/page"));
+    assert!(!decision.is_allowed());
+    assert_eq!(decision.get_reason(), DecisionReason::Rule);
+    assert_eq!(decision.get_user_agents(), ["googlebot", "bingbot"]);
+    assert!(!decision.is_default_group());
+    let rule = decision.get_rule().unwrap();
+    assert_eq!(rule.get_pattern(), "/search");
+    assert!(!rule.get_allowance());
+    assert_eq!(decision.get_line_no(), Some(6));
+    let decision = robots_txt.explain("Mozilla/5.0 (compatible; bingbot/2.0)", &url("/search/about"));
+    assert!(decision.is_allowed());
+    assert_eq!(decision.get_rule().unwrap().get_pattern(), "/search/about");
+    assert_eq!(decision.get_line_no(), Some(7));
+    let decision = robots_txt.explain("googlebot", &url("/public"));
+    assert!(decision.is_allowed());
+    assert_eq!(decision.get_rule().unwrap().get_pattern(), "*");
+    assert_eq!(decision.get_line_no(), Some(8));
+}
+
+#[test]
+fn test_explain_default_group() {
+    let robots_txt = parse_robots_txt(origin(), DOC).get_result();
+    let decision = robots_txt.explain("otherbot", &url("/private/page"));
+    assert!(!decision.is_allowed());
+    assert_eq!(decision.get_reason(), DecisionReason::Rule);
+    assert_eq!(decision.get_user_agents(), ["*"]);
+    assert!(decision.is_default_group());
+    assert_eq!(decision.get_line_no(), Some(2));
+    let decision = robots_txt.explain("otherbot", &url("/public"));
+    assert!(decision.is_allowed());
+    assert_eq!(decision.get_reason(), DecisionReason::NoMatchingRule);
+    assert!(decision.get_rule().is_none());
+    assert_eq!(decision.get_user_agents(), ["*"]);
+    assert!(decision.is_default_group());
+    assert_eq!(decision.get_group_spans().len(), 1);
+}
+
+#[test]
+fn test_explain_group_without_matching_rule() {
+    for evaluation_mode in [EvaluationMode::Legacy, EvaluationMode::Rfc9309].iter() {
+        let mut robots_txt = parse_robots_txt(origin(), "User-agent: googlebot\nDisallow: /private").get_result();
+        robots_txt.set_evaluation_mode(*evaluation_mode);
+        let decision = robots_txt.explain("googlebot", &url("/public"));
+        assert!(decision.is_allowed());
+        assert_eq!(decision.get_reason(), DecisionReason::NoMatchingRule);
+        assert_eq!(decision.get_user_agents(), ["googlebot"]);
+        assert!(!decision.is_default_group());
+        let decision = robots_txt.explain("otherbot", &url("/private"));
+        assert_eq!(decision.get_reason(), DecisionReason::NoMatchingRule);
+        assert!(decision.get_user_agents().is_empty());
+        assert!(decision.get_group_spans().is_empty());
+    }
+}
+
+/// Service that implements only the required methods of the trait.
+struct AllowAll;
+
+impl RobotsTxtService for AllowAll {
+    fn can_fetch(&self, _: &str, _: &Url) -> bool {
+        true
+    }
+
+    fn get_crawl_delay(&self, _: &str) -> Option<Duration> {
+        None
+    }
+
+    fn normalize_url(&self, _: &mut Url) -> bool {
+        true
+    }
+
+    fn normalize_url_ignore_origin(&self, _: &mut Url) {}
+
+    fn get_sitemaps(&self) -> &[Url] {
+        &[]
+    }
+
+    fn get_req_rate(&self, _: &str) -> Option<RequestRate> {
+        None
+    }
+}
+
+#[test]
+fn test_explain_default_implementation() {
+    let decision = AllowAll.explain("bot", &url("/private"));
+    assert!(decision.is_allowed());
+    assert_eq!(decision.get_reason(), DecisionReason::Unexplained);
+    assert!(decision.get_rule().is_none());
+}
+
+#[test]
+fn test_explain_agrees_with_can_fetch() {
+    for evaluation_mode in [EvaluationMode::Legacy, EvaluationMode::Rfc9309].iter() {
+        let mut robots_txt = parse_robots_txt(origin(), DOC).get_result();
+        robots_txt.set_evaluation_mode(*evaluation_mode);
+        for user_agent in ["googlebot", "bingbot", "otherbot"].iter() {
+            for path in ["/", "/private", "/search", "/search/about", "/robots.txt"].iter() {
+                let decision = robots_txt.explain(user_agent, &url(path));
+                assert_eq!(decision.is_allowed(), robots_txt.can_fetch(user_agent, &url(path)));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_explain_special_cases() {
+    let mut robots_txt = parse_robots_txt(origin(), "User-agent: *\nDisallow: /").get_result();
+    let decision = robots_txt.explain("bot", &Url::parse("https://other.com/").unwrap());
+    assert!(!decision.is_allowed());
+    assert_eq!(decision.get_reason(), DecisionReason::OriginMismatch);
+    assert!(!robots_txt.explain("bot", &url("/robots.txt")).is_allowed());
+    robots_txt.set_evaluation_mode(EvaluationMode::Rfc9309);
+    let decision = robots_txt.explain("bot", &url("/robots.txt"));
+    assert!(decision.is_allowed());
+    assert_eq!(decision.get_reason(), DecisionReason::RobotsTxtPath);
+}
+
+#[test]
+fn test_explain_fetch_status() {
+    let robots_txt = parse_fetched_robots_txt(origin(), 403, DOC).get_result();
+    let decision = robots_txt.explain("bot", &url("/"));
+    assert!(!decision.is_allowed());
+    assert_eq!(decision.get_reason(), DecisionReason::FetchStatus(FetchStatus::Denied));
+    let robots_txt = parse_fetched_robots_txt(origin(), 404, DOC).get_result();
+    let decision = robots_txt.explain("bot", &url("/private"));
+    assert!(decision.is_allowed());
+    assert_eq!(decision.get_reason(), DecisionReason::FetchStatus(FetchStatus::Failed));
+    let robots_txt =
+        parse_fetched_robots_txt_with_metadata(origin(), FetchMetadata::new(503), "", &FetchPolicy::rfc9309())
+            .get_result();
+    let decision = robots_txt.explain("bot", &url("/"));
+    assert!(!decision.is_allowed());
+    assert_eq!(
+        decision.get_reason(),
+        DecisionReason::FetchStatus(FetchStatus::Unreachable)
+    );
+    let robots_txt = parse_fetched_robots_txt(origin(), 200, DOC).get_result();
+    let decision = robots_txt.explain("bot", &url("/private"));
+    assert_eq!(decision.get_reason(), DecisionReason::Rule);
+    assert_eq!(decision.get_line_no(), Some(2));
+}
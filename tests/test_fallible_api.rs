@@ -0,0 +1,80 @@
+mod common;
+
+use common::origin;
+use robotparser::model::{ErrorClass, ErrorKind, FetchedRobotsTxt, Path};
+use robotparser::parser::{parse_fetched_robots_txt, parse_robots_txt};
+use robotparser::service::RobotsTxtService;
+use url::Url;
+
+const INPUT: &str = "User-agent: *\nDisallow: /private\nClean-param: y";
+
+fn urls_without_path() -> Vec<Url> {
+    vec![
+        Url::parse("mailto:x?y=1").unwrap(),
+        Url::parse("data:text/plain,hello?y=1").unwrap(),
+        Url::parse("javascript:void(0)").unwrap(),
+    ]
+}
+
+fn assert_url_without_path(result: Result<bool, robotparser::model::Error>, url: &Url) {
+    let err = result.unwrap_err();
+    assert_eq!(err.get_class(), ErrorClass::InvalidUrl);
+    assert!(!err.is_transient());
+    assert!(matches!(err.kind, ErrorKind::UrlWithoutPath(ref item) if item == url));
+}
+
+#[test]
+fn test_robots_txt_urls_without_path() {
+    let robots_txt = parse_robots_txt(origin(), INPUT).get_result();
+    for url in urls_without_path() {
+        assert!(Path::try_from_url(&url).is_err());
+        assert!(!robots_txt.can_fetch("*", &url));
+        assert_url_without_path(robots_txt.try_can_fetch("*", &url), &url);
+        let mut normalized_url = url.clone();
+        assert!(!robots_txt.normalize_url(&mut normalized_url));
+        robots_txt.normalize_url_ignore_origin(&mut normalized_url);
+        let mut normalized_url = url.clone();
+        assert_url_without_path(robots_txt.try_normalize_url(&mut normalized_url), &url);
+        assert_eq!(normalized_url, url);
+    }
+}
+
+#[test]
+fn test_fetched_robots_txt_urls_without_path() {
+    let fetched_robots_txt: FetchedRobotsTxt = parse_fetched_robots_txt(origin(), 200, INPUT).get_result();
+    let unavailable_robots_txt: FetchedRobotsTxt = parse_fetched_robots_txt(origin(), 404, "").get_result();
+    for robots_txt in [fetched_robots_txt, unavailable_robots_txt].iter() {
+        for url in urls_without_path() {
+            assert_url_without_path(robots_txt.try_can_fetch("*", &url), &url);
+            let mut normalized_url = url.clone();
+            robots_txt.normalize_url_ignore_origin(&mut normalized_url);
+            let expected_url = normalized_url.clone();
+            assert_url_without_path(robots_txt.try_normalize_url(&mut normalized_url), &expected_url);
+        }
+    }
+}
+
+#[test]
+fn test_try_variants_for_valid_urls() {
+    let robots_txt = parse_robots_txt(origin(), INPUT).get_result();
+    let url = Url::parse("https://example.com/private").unwrap();
+    assert!(!robots_txt.try_can_fetch("*", &url).unwrap());
+    let url = Url::parse("https://example.com/public").unwrap();
+    assert!(robots_txt.try_can_fetch("*", &url).unwrap());
+    let mut url = Url::parse("https://example.com/page?x=1&y=2#top").unwrap();
+    assert!(robots_txt.try_normalize_url(&mut url).unwrap());
+    assert_eq!(url.as_str(), "https://example.com/page?x=1");
+    let mut url = Url::parse("https://other.com/page?y=2").unwrap();
+    assert!(!robots_txt.try_normalize_url(&mut url).unwrap());
+    assert!(!robots_txt.try_can_fetch("*", &url).unwrap());
+}
+
+#[test]
+fn test_user_agent_lines_without_group() {
+    let input = "User-agent: a\nUser-agent: b\nUser-agent: a\nDisallow: /\n\nUser-agent:\nUser-agent: *";
+    let robots_txt = parse_robots_txt(origin(), input).get_result();
+    let url = Url::parse("https://example.com/page").unwrap();
+    assert!(!robots_txt.can_fetch("a", &url));
+    assert!(!robots_txt.can_fetch("b", &url));
+    assert!(robots_txt.can_fetch("c", &url));
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use common::{policy, TestResponse, TestServer};
+use robotparser::http::RobotsTxtClient;
+use robotparser::model::{FetchMetadata, FetchStatus};
+use robotparser::parser::{parse_fetched_robots_txt, parse_fetched_robots_txt_with_metadata};
+use tokio::runtime::Runtime;
+use url::Url;
+
+const DOC: &str = "User-agent: *\nDisallow: /private";
+
+#[test]
+fn test_fetch_status() {
+    let origin = Url::parse("https://example.com/robots.txt").unwrap().origin();
+    let status = |status_code| {
+        parse_fetched_robots_txt(origin.clone(), status_code, DOC)
+            .get_result()
+            .get_status()
+    };
+    assert_eq!(status(200), FetchStatus::Fetched);
+    assert_eq!(status(401), FetchStatus::Denied);
+    assert_eq!(status(403), FetchStatus::Denied);
+    assert_eq!(status(404), FetchStatus::Failed);
+    assert_eq!(status(500), FetchStatus::Failed);
+    let robots_txt =
+        parse_fetched_robots_txt_with_metadata(origin.clone(), FetchMetadata::new(503), "", &policy()).get_result();
+    assert_eq!(robots_txt.get_status(), FetchStatus::Unreachable);
+    assert_eq!(robots_txt.get_status_code(), Some(503));
+    let robots_txt =
+        parse_fetched_robots_txt_with_metadata(origin, FetchMetadata::without_response(), "", &policy()).get_result();
+    assert_eq!(robots_txt.get_status(), FetchStatus::Unreachable);
+    assert_eq!(robots_txt.get_status_code(), None);
+    assert_eq!(robots_txt.get_metadata().get_body_size(), None);
+}
+
+#[test]
+fn test_fetch_metadata_content_hash() {
+    let origin = Url::parse("https://example.com/robots.txt").unwrap().origin();
+    let metadata = |status_code, body| {
+        parse_fetched_robots_txt(origin.clone(), status_code, body)
+            .get_result()
+            .get_metadata()
+            .clone()
+    };
+    let first = metadata(200, DOC);
+    assert_eq!(first.get_body_size(), Some(DOC.len()));
+    assert_eq!(first.get_content_hash(), metadata(200, DOC).get_content_hash());
+    assert_ne!(
+        first.get_content_hash(),
+        metadata(200, "User-agent: *").get_content_hash()
+    );
+    let denied = metadata(403, "Forbidden");
+    assert_eq!(denied.get_body_size(), Some(9));
+    assert!(denied.get_content_hash().is_some());
+    assert_eq!(
+        FetchMetadata::new(200).with_body("").get_content_hash(),
+        Some(0xcbf2_9ce4_8422_2325)
+    );
+}
+
+#[test]
+fn test_fetch_metadata_from_client() {
+    let server = TestServer::start(vec![
+        TestResponse::new(301, "").with_header("Location", "/moved/robots.txt"),
+        TestResponse::new(200, DOC)
+            .with_header("Content-Type", "text/plain")
+            .with_header("X-Ignored", "1"),
+        TestResponse::new(304, ""),
+    ]);
+    let client = reqwest::blocking::Client::new();
+    let robots_txt = client
+        .fetch_robots_txt_with_policy(server.origin(), &policy())
+        .unwrap()
+        .get_result();
+    let metadata = robots_txt.get_metadata();
+    assert_eq!(robots_txt.get_status(), FetchStatus::Fetched);
+    assert_eq!(robots_txt.get_status_code(), Some(200));
+    assert_eq!(metadata.get_redirects(), &[server.url("/robots.txt")]);
+    assert_eq!(metadata.get_final_url(), Some(&server.url("/moved/robots.txt")));
+    assert!(metadata.get_latency().is_some());
+    assert_eq!(metadata.get_body_size(), Some(DOC.len()));
+    assert_eq!(metadata.get_header("content-type"), Some("text/plain"));
+    assert_eq!(metadata.get_header("x-ignored"), None);
+    assert_eq!(robots_txt.get_body(), Some(DOC));
+
+    let runtime = Runtime::new().unwrap();
+    let revalidated = runtime
+        .block_on(
+            reqwest::Client::new()
+                .revalidate_robots_txt_with_policy(server.origin(), &robots_txt, &policy())
+                .unwrap(),
+        )
+        .unwrap()
+        .get_result();
+    let revalidated_metadata = revalidated.get_metadata();
+    assert_eq!(revalidated.get_status(), FetchStatus::Fetched);
+    assert_eq!(revalidated_metadata.get_redirects(), &[] as &[Url]);
+    assert_eq!(revalidated_metadata.get_final_url(), Some(&server.url("/robots.txt")));
+    assert_eq!(revalidated_metadata.get_body_size(), Some(DOC.len()));
+    assert_eq!(revalidated_metadata.get_content_hash(), metadata.get_content_hash());
+}
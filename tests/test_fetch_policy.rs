@@ -0,0 +1,159 @@
+mod common;
+
+use common::{policy, TestResponse, TestServer};
+use reqwest::blocking::Client;
+use robotparser::http::RobotsTxtClient;
+use robotparser::model::{FetchOutcome, FetchPolicy, FetchedRobotsTxt, RetryPolicy, StatusClass};
+use robotparser::parser::{parse_fetched_robots_txt, parse_fetched_robots_txt_with_policy};
+use robotparser::service::RobotsTxtService;
+use std::time::Duration;
+use url::{Origin, Url};
+
+const DOC: &str = "User-agent: *\nDisallow: /private";
+
+fn can_fetch(status_code: u16, policy: &FetchPolicy, path: &str) -> bool {
+    let url = Url::parse("https://www.example.com/robots.txt").unwrap();
+    let robots_txt = parse_fetched_robots_txt_with_policy(url.origin(), status_code, DOC, policy).get_result();
+    robots_txt.can_fetch("foobot", &url.join(path).unwrap())
+}
+
+/// Returns `(public, private)` decisions for the status code.
+fn decisions(status_code: u16, policy: &FetchPolicy) -> (bool, bool) {
+    (
+        can_fetch(status_code, policy, "/public"),
+        can_fetch(status_code, policy, "/private"),
+    )
+}
+
+#[test]
+fn test_status_class() {
+    assert_eq!(StatusClass::from_status_code(100), StatusClass::Informational);
+    assert_eq!(StatusClass::from_status_code(204), StatusClass::Success);
+    assert_eq!(StatusClass::from_status_code(301), StatusClass::Redirection);
+    assert_eq!(StatusClass::from_status_code(404), StatusClass::ClientError);
+    assert_eq!(StatusClass::from_status_code(503), StatusClass::ServerError);
+    assert_eq!(StatusClass::from_status_code(600), StatusClass::Unknown);
+}
+
+#[test]
+fn test_legacy_policy() {
+    let policy = FetchPolicy::legacy();
+    assert_eq!(decisions(200, &policy), (true, false));
+    assert_eq!(decisions(203, &policy), (true, true));
+    assert_eq!(decisions(401, &policy), (false, false));
+    assert_eq!(decisions(403, &policy), (false, false));
+    assert_eq!(decisions(404, &policy), (true, true));
+    assert_eq!(decisions(429, &policy), (true, true));
+    assert_eq!(decisions(503, &policy), (true, true));
+}
+
+#[test]
+fn test_default_policy_is_legacy() {
+    let url = Url::parse("https://www.example.com/robots.txt").unwrap();
+    for status_code in [200, 203, 401, 403, 404, 429, 500, 503].iter() {
+        let robots_txt = parse_fetched_robots_txt(url.origin(), *status_code, DOC).get_result();
+        let expected = decisions(*status_code, &FetchPolicy::legacy());
+        let actual = (
+            robots_txt.can_fetch("foobot", &url.join("/public").unwrap()),
+            robots_txt.can_fetch("foobot", &url.join("/private").unwrap()),
+        );
+        assert_eq!(expected, actual, "{}", status_code);
+    }
+}
+
+#[test]
+fn test_rfc9309_policy() {
+    let policy = FetchPolicy::rfc9309();
+    assert_eq!(decisions(200, &policy), (true, false));
+    assert_eq!(decisions(203, &policy), (true, false));
+    assert_eq!(decisions(308, &policy), (true, true));
+    assert_eq!(decisions(401, &policy), (true, true));
+    assert_eq!(decisions(403, &policy), (true, true));
+    assert_eq!(decisions(404, &policy), (true, true));
+    assert_eq!(decisions(429, &policy), (true, true));
+    assert_eq!(decisions(500, &policy), (false, false));
+    assert_eq!(decisions(503, &policy), (false, false));
+    assert_eq!(policy.get_outcome(503), FetchOutcome::Unreachable);
+}
+
+#[test]
+fn test_google_policy() {
+    let policy = FetchPolicy::google();
+    assert_eq!(decisions(200, &policy), (true, false));
+    assert_eq!(decisions(403, &policy), (true, true));
+    assert_eq!(decisions(429, &policy), (false, false));
+    assert_eq!(decisions(503, &policy), (false, false));
+}
+
+#[test]
+fn test_custom_policy() {
+    let policy = FetchPolicy::rfc9309()
+        .with_status_class(StatusClass::ClientError, FetchOutcome::DisallowAll)
+        .with_status_code(404, FetchOutcome::AllowAll)
+        .with_status_code(404, FetchOutcome::Parse);
+    assert_eq!(policy.get_outcome(403), FetchOutcome::DisallowAll);
+    assert_eq!(policy.get_outcome(404), FetchOutcome::Parse);
+    assert_eq!(decisions(404, &policy), (true, false));
+}
+
+#[test]
+fn test_retryable_status_codes() {
+    let policy = FetchPolicy::rfc9309();
+    assert!(policy.is_retryable_status_code(429));
+    assert!(policy.is_retryable_status_code(503));
+    assert!(!policy.is_retryable_status_code(404));
+    let policy = FetchPolicy::rfc9309()
+        .with_status_code(503, FetchOutcome::AllowAll)
+        .with_status_code(429, FetchOutcome::DisallowAll);
+    assert!(!policy.is_retryable_status_code(503));
+    assert!(!policy.is_retryable_status_code(429));
+    assert!(policy.is_retryable_status_code(500));
+    let policy = FetchPolicy::rfc9309().with_status_class(StatusClass::ServerError, FetchOutcome::AllowAll);
+    assert!(!policy.is_retryable_status_code(500));
+    assert!(FetchPolicy::google().is_retryable_status_code(429));
+
+    let server = TestServer::start(vec![TestResponse::new(503, DOC), TestResponse::new(200, DOC)]);
+    let policy = FetchPolicy::rfc9309()
+        .with_status_code(503, FetchOutcome::AllowAll)
+        .with_retry_policy(RetryPolicy::new(1).with_initial_backoff(Duration::from_millis(1)));
+    let robots_txt = Client::new()
+        .fetch_robots_txt_with_policy(server.origin(), &policy)
+        .unwrap()
+        .get_result();
+    assert!(robots_txt.can_fetch("foobot", &server.url("/private")));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn test_client_with_policy() {
+    let server = TestServer::start(vec![TestResponse::new(503, DOC)]);
+    let client = Client::new();
+    let robots_txt = client.fetch_robots_txt(server.origin()).unwrap().get_result();
+    assert!(robots_txt.can_fetch("foobot", &server.url("/public")));
+    let robots_txt = client
+        .fetch_robots_txt_with_policy(server.origin(), &policy())
+        .unwrap()
+        .get_result();
+    assert!(!robots_txt.can_fetch("foobot", &server.url("/public")));
+    assert_eq!(server.requests()[0][0], "GET /robots.txt HTTP/1.1");
+}
+
+/// Client that implements only the required method of the trait.
+struct StaticClient;
+
+impl RobotsTxtClient for StaticClient {
+    type Result = FetchedRobotsTxt;
+
+    fn fetch_robots_txt(&self, origin: Origin) -> Self::Result {
+        parse_fetched_robots_txt(origin, 200, DOC).get_result()
+    }
+}
+
+#[test]
+fn test_client_without_policy_support() {
+    let url = Url::parse("https://www.example.com/private").unwrap();
+    let robots_txt = StaticClient.fetch_robots_txt_with_policy(url.origin(), &FetchPolicy::rfc9309());
+    assert!(!robots_txt.can_fetch("foobot", &url));
+    let robots_txt = StaticClient.revalidate_robots_txt(url.origin(), &robots_txt);
+    assert!(!robots_txt.can_fetch("foobot", &url));
+}
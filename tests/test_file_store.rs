@@ -0,0 +1,246 @@
+#![cfg(feature = "file-store")]
+
+use robotparser::cache::{FileRobotsStore, RobotsCache};
+use robotparser::model::{Clock, EvaluationMode, FetchMetadata, FetchPolicy, FetchedRobotsTxt};
+use robotparser::parser::{parse_fetched_robots_txt, parse_fetched_robots_txt_with_metadata};
+use robotparser::service::RobotsTxtService;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use url::{Origin, Url};
+
+const DOC: &str = "User-agent: *\nDisallow: /private\n\nSitemap: https://example.com/sitemap.xml";
+const MAX_SIZE: u64 = 1024 * 1024;
+
+/// Clock that always returns the same time.
+struct FixedClock(SystemTime);
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+/// Returns the empty directory for the test.
+fn directory(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("robotparser-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&directory);
+    directory
+}
+
+fn origin(host: &str) -> Origin {
+    Url::parse(&format!("https://{}/", host)).unwrap().origin()
+}
+
+fn robots_txt(host: &str) -> Arc<FetchedRobotsTxt> {
+    Arc::new(parse_fetched_robots_txt(origin(host), 200, DOC).get_result())
+}
+
+#[test]
+fn test_file_store_survives_restart() {
+    let directory = directory("restart");
+    let fetched_at = SystemTime::now() - Duration::from_secs(60);
+    let metadata = FetchMetadata::new(200)
+        .with_header("ETag", "\"v1\"")
+        .with_header("Cache-Control", "max-age=3600")
+        .with_redirects(vec![Url::parse("http://example.com/robots.txt").unwrap()])
+        .with_final_url(Url::parse("https://example.com/robots.txt").unwrap())
+        .with_latency(Duration::from_millis(125))
+        .with_fetched_at(fetched_at);
+    let origin = origin("example.com");
+    let robots_txt = parse_fetched_robots_txt_with_metadata(origin.clone(), metadata, DOC, &FetchPolicy::default());
+    let store = FileRobotsStore::open(&directory, MAX_SIZE).unwrap();
+    assert!(store.is_empty());
+    store.insert(origin.clone(), Arc::new(robots_txt.get_result()));
+    assert_eq!(store.len(), 1);
+    drop(store);
+
+    let store = FileRobotsStore::open(&directory, MAX_SIZE).unwrap();
+    assert_eq!(store.len(), 1);
+    assert!(store.get_size() > DOC.len() as u64);
+    let url = Url::parse("https://example.com/private/page").unwrap();
+    let robots_txt = store.get(&url).unwrap();
+    assert!(!robots_txt.can_fetch("*", &url));
+    assert!(robots_txt.can_fetch("*", &Url::parse("https://example.com/public").unwrap()));
+    assert_eq!(robots_txt.get_sitemaps().len(), 1);
+    assert_eq!(robots_txt.get_body(), Some(DOC));
+    assert_eq!(robots_txt.get_etag(), Some("\"v1\""));
+    assert_eq!(robots_txt.get_metadata().get_status_code(), Some(200));
+    assert_eq!(*robots_txt.get_fetched_at(), fetched_at);
+    let metadata = robots_txt.get_metadata();
+    assert_eq!(metadata.get_redirects().len(), 1);
+    assert_eq!(
+        metadata.get_final_url().unwrap().as_str(),
+        "https://example.com/robots.txt"
+    );
+    assert_eq!(metadata.get_latency(), Some(Duration::from_millis(125)));
+    assert_eq!(metadata.get_body_size(), Some(DOC.len()));
+    assert_eq!(robots_txt.get_cache_lifetime(), Duration::from_secs(3600));
+    assert!(store.get(&Url::parse("http://example.com/").unwrap()).is_none());
+    assert!(store.remove(&origin).is_some());
+    assert!(store.is_empty());
+    assert!(store.get(&url).is_none());
+    let _ = fs::remove_dir_all(&directory);
+}
+
+#[test]
+fn test_file_store_status_outcomes() {
+    let directory = directory("outcomes");
+    let store = FileRobotsStore::open(&directory, MAX_SIZE)
+        .unwrap()
+        .with_policy(FetchPolicy::rfc9309());
+    let denied = parse_fetched_robots_txt(origin("denied.com"), 403, "Forbidden").get_result();
+    store.insert(origin("denied.com"), Arc::new(denied));
+    let unreachable = parse_fetched_robots_txt_with_metadata(
+        origin("unreachable.com"),
+        FetchMetadata::without_response(),
+        "",
+        &FetchPolicy::rfc9309(),
+    )
+    .get_result();
+    store.insert(origin("unreachable.com"), Arc::new(unreachable));
+
+    let store = FileRobotsStore::open(&directory, MAX_SIZE)
+        .unwrap()
+        .with_policy(FetchPolicy::rfc9309());
+    // The outcome of the fetch is stored, so the policy of the store does not reinterpret the status code.
+    let denied = store.get_by_origin(&origin("denied.com")).unwrap();
+    assert_eq!(denied.get_metadata().get_status_code(), Some(403));
+    assert_eq!(denied.get_metadata().get_body_size(), Some(9));
+    assert!(!denied.can_fetch("*", &Url::parse("https://denied.com/page").unwrap()));
+    let unreachable = store.get_by_origin(&origin("unreachable.com")).unwrap();
+    assert!(unreachable.is_unreachable());
+    assert_eq!(unreachable.get_metadata().get_status_code(), None);
+    assert!(!unreachable.can_fetch("*", &Url::parse("https://unreachable.com/page").unwrap()));
+    store.clear();
+    assert!(store.is_empty());
+    assert_eq!(fs::read_dir(&directory).unwrap().count(), 0);
+    let _ = fs::remove_dir_all(&directory);
+}
+
+#[test]
+fn test_file_store_expiry() {
+    let directory = directory("expiry");
+    let day = Duration::from_secs(24 * 60 * 60);
+    let store = FileRobotsStore::open(&directory, MAX_SIZE)
+        .unwrap()
+        .with_clock(FixedClock(SystemTime::now() + day + Duration::from_secs(60)));
+    store.insert(origin("example.com"), robots_txt("example.com"));
+    assert!(store.get_by_origin(&origin("example.com")).is_none());
+    assert!(store.get_stale_by_origin(&origin("example.com")).is_some());
+    let _ = fs::remove_dir_all(&directory);
+}
+
+#[test]
+fn test_file_store_size_eviction() {
+    let directory = directory("eviction");
+    let store = FileRobotsStore::open(&directory, MAX_SIZE).unwrap();
+    store.insert(origin("a.com"), robots_txt("a.com"));
+    let file_size = store.get_size();
+    let store = FileRobotsStore::open(&directory, file_size * 2).unwrap();
+    store.insert(origin("b.com"), robots_txt("b.com"));
+    assert!(store.get_by_origin(&origin("a.com")).is_some());
+    store.insert(origin("c.com"), robots_txt("c.com"));
+    assert_eq!(store.len(), 2);
+    assert!(store.get_size() <= file_size * 2);
+    assert!(store.get_by_origin(&origin("a.com")).is_some());
+    assert!(store.get_by_origin(&origin("b.com")).is_none());
+    assert!(store.get_by_origin(&origin("c.com")).is_some());
+    let store = FileRobotsStore::open(&directory, file_size).unwrap();
+    assert_eq!(store.len(), 1);
+    assert_eq!(fs::read_dir(&directory).unwrap().count(), 1);
+    let _ = fs::remove_dir_all(&directory);
+}
+
+#[test]
+fn test_file_store_damaged_files() {
+    let directory = directory("damaged");
+    let store = FileRobotsStore::open(&directory, MAX_SIZE).unwrap();
+    store.insert(origin("example.com"), robots_txt("example.com"));
+    let file = fs::read_dir(&directory).unwrap().next().unwrap().unwrap().path();
+    fs::write(file.with_extension("1.tmp"), "interrupted write").unwrap();
+    fs::write(&file, "garbage").unwrap();
+
+    let store = FileRobotsStore::open(&directory, MAX_SIZE).unwrap();
+    assert_eq!(store.len(), 1);
+    assert!(store.get_by_origin(&origin("example.com")).is_none());
+    assert!(store.is_empty());
+    assert_eq!(fs::read_dir(&directory).unwrap().count(), 0);
+    let _ = fs::remove_dir_all(&directory);
+}
+
+#[test]
+fn test_file_store_keeps_outcome_and_evaluation_mode() {
+    let directory = directory("outcome");
+    let store = FileRobotsStore::open(&directory, MAX_SIZE).unwrap();
+    let since = SystemTime::UNIX_EPOCH + Duration::from_secs(1_445_412_480);
+    let policy = FetchPolicy::rfc9309().with_unreachable_timeout(Duration::from_secs(3600));
+    let metadata = FetchMetadata::new(503).with_fetched_at(since + Duration::from_secs(60));
+    let unreachable = parse_fetched_robots_txt_with_metadata(origin("unreachable.com"), metadata, "", &policy)
+        .get_result()
+        .with_previous(
+            &parse_fetched_robots_txt_with_metadata(
+                origin("unreachable.com"),
+                FetchMetadata::new(503).with_fetched_at(since),
+                "",
+                &policy,
+            )
+            .get_result(),
+        );
+    store.insert(origin("unreachable.com"), Arc::new(unreachable));
+    let mut parsed = parse_fetched_robots_txt(
+        origin("example.com"),
+        200,
+        "User-agent: *\nDisallow: /\nAllow: /robots.txt",
+    )
+    .get_result();
+    parsed.set_evaluation_mode(EvaluationMode::Rfc9309);
+    store.insert(origin("example.com"), Arc::new(parsed));
+
+    // The default policy of the store allows crawling after the `503` status code.
+    let store = FileRobotsStore::open(&directory, MAX_SIZE).unwrap();
+    let unreachable = store.get_stale_by_origin(&origin("unreachable.com")).unwrap();
+    assert!(unreachable.is_unreachable());
+    assert_eq!(unreachable.get_unreachable_since(), Some(since));
+    assert!(!unreachable.is_unreachable_timeout_elapsed(&FixedClock(since + Duration::from_secs(3599))));
+    assert!(unreachable.is_unreachable_timeout_elapsed(&FixedClock(since + Duration::from_secs(3600))));
+    assert!(!unreachable.can_fetch_at(
+        "*",
+        &Url::parse("https://unreachable.com/").unwrap(),
+        &FixedClock(since)
+    ));
+    let parsed = store.get_by_origin(&origin("example.com")).unwrap();
+    assert!(parsed.can_fetch("*", &Url::parse("https://example.com/robots.txt").unwrap()));
+    assert!(!parsed.can_fetch("*", &Url::parse("https://example.com/page").unwrap()));
+    let _ = fs::remove_dir_all(&directory);
+}
+
+#[test]
+fn test_file_store_memory_layer() {
+    let directory = directory("memory");
+    let store = FileRobotsStore::open(&directory, MAX_SIZE).unwrap();
+    let robots_txt = robots_txt("example.com");
+    store.insert(origin("example.com"), robots_txt.clone());
+    // Hot lookups return the robots.txt file that is kept in memory without reading the file.
+    let file = fs::read_dir(&directory).unwrap().next().unwrap().unwrap().path();
+    fs::write(&file, "garbage").unwrap();
+    assert!(Arc::ptr_eq(
+        &store.get_by_origin(&origin("example.com")).unwrap(),
+        &robots_txt
+    ));
+    let _ = fs::remove_dir_all(&directory);
+}
+
+#[test]
+fn test_file_store_without_memory_layer() {
+    let directory = directory("no-memory");
+    let store = FileRobotsStore::open(&directory, MAX_SIZE)
+        .unwrap()
+        .with_memory_entries(0);
+    let robots_txt = robots_txt("example.com");
+    store.insert(origin("example.com"), robots_txt.clone());
+    let loaded = store.get_by_origin(&origin("example.com")).unwrap();
+    assert!(!Arc::ptr_eq(&loaded, &robots_txt));
+    let _ = fs::remove_dir_all(&directory);
+}
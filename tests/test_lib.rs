@@ -1,4 +1,7 @@
-use robotparser::parser::parse_robots_txt;
+#![allow(unexpected_cfgs)]
+
+use robotparser::model::EvaluationMode;
+use robotparser::parser::{parse_robots_txt, parse_robots_txt_with_options, ParserOptions};
 use robotparser::service::RobotsTxtService;
 use std::time::Duration;
 use url::Url;
@@ -6,8 +9,12 @@ use url::Url;
 const AGENT: &str = "test_robotparser";
 
 fn robot_test(doc: &str, good_urls: Vec<&str>, bad_urls: Vec<&str>, agent: &str) {
+    robot_test_with_options(doc, good_urls, bad_urls, agent, &ParserOptions::default());
+}
+
+fn robot_test_with_options(doc: &str, good_urls: Vec<&str>, bad_urls: Vec<&str>, agent: &str, options: &ParserOptions) {
     let url = Url::parse("https://www.baidu.com/robots.txt").unwrap();
-    let parser = parse_robots_txt(url.origin(), doc).get_result();
+    let parser = parse_robots_txt_with_options(url.origin(), doc, options).get_result();
     for url in &good_urls {
         let url = format!("https://www.baidu.com{}", url);
         let url = Url::parse(&url).unwrap();
@@ -193,6 +200,37 @@ fn test_robots_txt_10() {
     robot_test(doc, good, bad, "googlebot");
 }
 
+/// merge groups of the same user agent in the RFC 9309 mode
+#[test]
+fn test_robots_txt_merge_groups() {
+    let options = ParserOptions::default().with_evaluation_mode(EvaluationMode::Rfc9309);
+    let doc = "\n\
+    User-agent: Googlebot\n\
+    Disallow: /a\n\
+    \n\
+    User-agent: Bingbot\n\
+    User-agent: googlebot\n\
+    Disallow: /b\n\
+    Crawl-delay: 3\n\
+    \n\
+    User-agent: Googlebot\n\
+    Crawl-delay: 5\n\
+    Request-rate: 1/5\n\
+    ";
+    let good = vec!["/"];
+    let bad = vec!["/a", "/b"];
+    robot_test_with_options(doc, good, bad, "Googlebot", &options);
+    let good = vec!["/", "/a"];
+    let bad = vec!["/b"];
+    robot_test_with_options(doc, good, bad, "Bingbot", &options);
+
+    let robots_txt_url = Url::parse("https://www.baidu.com/robots.txt").unwrap();
+    let parser = parse_robots_txt_with_options(robots_txt_url.origin(), doc, &options).get_result();
+    assert_eq!(Some(Duration::new(3, 0)), parser.get_crawl_delay("Googlebot"));
+    assert_eq!(1, parser.get_req_rate("Googlebot").unwrap().requests);
+    assert!(parser.get_req_rate("Bingbot").is_none());
+}
+
 /// query string support
 #[test]
 fn test_robots_txt_11() {
@@ -220,6 +258,22 @@ fn test_robots_txt_12() {
     robot_test_simple(doc, good, bad);
 }
 
+/// merge all * entries in the RFC 9309 mode
+#[test]
+fn test_robots_txt_12_rfc9309() {
+    let doc = "\n\
+    User-agent: *\n\
+    Disallow: /some/path\n\
+    \n\
+    User-agent: *\n\
+    Disallow: /another/path\n\
+    ";
+    let good = vec!["/"];
+    let bad = vec!["/some/path", "/another/path"];
+    let options = ParserOptions::default().with_evaluation_mode(EvaluationMode::Rfc9309);
+    robot_test_with_options(doc, good, bad, AGENT, &options);
+}
+
 /// Empty query. Normalizing the url first.
 #[test]
 fn test_robots_txt_13() {
@@ -0,0 +1,206 @@
+mod common;
+
+use common::origin;
+use robotparser::model::EvaluationMode;
+use robotparser::parser::{
+    parse_robots_txt, parse_robots_txt_with_limits, parse_robots_txt_with_options, ParserLimits, ParserOptions,
+    WarningReason,
+};
+use robotparser::service::RobotsTxtService;
+use url::Url;
+
+fn url(path: &str) -> Url {
+    Url::parse("https://example.com").unwrap().join(path).unwrap()
+}
+
+/// Returns the options of the RFC 9309 mode, which merges the groups of the same user agent, with the limits.
+fn rfc9309_options(limits: ParserLimits) -> ParserOptions {
+    ParserOptions::default()
+        .with_evaluation_mode(EvaluationMode::Rfc9309)
+        .with_limits(limits)
+}
+
+#[test]
+fn test_default_limits() {
+    let limits = ParserLimits::default();
+    assert_eq!(limits.get_max_lines(), 100_000);
+    assert_eq!(limits.get_max_rules_per_group(), 10_000);
+    assert_eq!(limits.get_max_groups(), 10_000);
+    assert_eq!(limits.get_max_rules(), 100_000);
+    assert_eq!(limits.get_max_pattern_length(), 2048);
+    assert_eq!(limits.get_max_wildcards_per_pattern(), 16);
+    let input = format!("User-agent: *\nDisallow: /{}\nDisallow: /b", "a".repeat(4096));
+    let result = parse_robots_txt(origin(), &input);
+    assert!(matches!(
+        result.get_warnings()[0].get_reason(),
+        WarningReason::PatternTooLong { max_length: 2048 }
+    ));
+    let result = parse_robots_txt_with_limits(origin(), &input, &ParserLimits::unlimited());
+    assert!(result.get_warnings().is_empty());
+    assert!(!result
+        .get_result()
+        .can_fetch("*", &url(&format!("/{}", "a".repeat(4096)))));
+}
+
+#[test]
+fn test_max_lines() {
+    let limits = ParserLimits::default().with_max_lines(2);
+    let input = "User-agent: *\nDisallow: /a\nDisallow: /b\nDisallow: /c";
+    let result = parse_robots_txt_with_limits(origin(), input, &limits);
+    assert_eq!(result.get_warnings().len(), 1);
+    let warning = &result.get_warnings()[0];
+    assert!(matches!(
+        warning.get_reason(),
+        WarningReason::TooManyLines { max_lines: 2 }
+    ));
+    assert_eq!(warning.get_line_no(), 3);
+    assert_eq!(warning.get_line_text(), "Disallow: /b");
+    let robots_txt = result.get_result();
+    assert!(!robots_txt.can_fetch("*", &url("/a")));
+    assert!(robots_txt.can_fetch("*", &url("/b")));
+    assert!(robots_txt.can_fetch("*", &url("/c")));
+}
+
+#[test]
+fn test_max_rules_per_merged_group() {
+    let limits = ParserLimits::default().with_max_rules_per_group(10);
+    let input = "User-agent: a\nDisallow: /x\n".repeat(50);
+    let result = parse_robots_txt_with_options(origin(), &input, &rfc9309_options(limits));
+    let too_many_rules: Vec<_> = result
+        .get_warnings()
+        .iter()
+        .filter(|warning| matches!(warning.get_reason(), WarningReason::TooManyRules { .. }))
+        .collect();
+    assert_eq!(too_many_rules.len(), 1);
+    assert!(matches!(
+        too_many_rules[0].get_reason(),
+        WarningReason::TooManyRules { max_rules: 10 }
+    ));
+    assert_eq!(too_many_rules[0].get_line_no(), 22);
+    assert_eq!(too_many_rules[0].get_line_text(), "Disallow: /x");
+    let robots_txt = result.get_result();
+    assert_eq!(robots_txt.get_groups().len(), 1);
+    assert_eq!(robots_txt.get_groups()[0].get_rules().len(), 10);
+    assert!(!robots_txt.can_fetch("a", &url("/x")));
+}
+
+#[test]
+fn test_max_rules_per_group() {
+    let limits = ParserLimits::default().with_max_rules_per_group(2);
+    let input = "User-agent: a\nDisallow: /a\nDisallow: /b\nDisallow: /c\n\nUser-agent: b\nDisallow: /c";
+    let result = parse_robots_txt_with_limits(origin(), input, &limits);
+    assert_eq!(result.get_warnings().len(), 1);
+    let warning = &result.get_warnings()[0];
+    assert!(matches!(
+        warning.get_reason(),
+        WarningReason::TooManyRules { max_rules: 2 }
+    ));
+    assert_eq!(warning.get_line_no(), 4);
+    let robots_txt = result.get_result();
+    assert!(!robots_txt.can_fetch("a", &url("/b")));
+    assert!(robots_txt.can_fetch("a", &url("/c")));
+    assert!(!robots_txt.can_fetch("b", &url("/c")));
+}
+
+#[test]
+fn test_max_groups() {
+    let limits = ParserLimits::default().with_max_groups(2);
+    let input = "User-agent: a\nDisallow: /a\n\nUser-agent: b\nDisallow: /b\n\nUser-agent: c\nDisallow: /c";
+    let result = parse_robots_txt_with_limits(origin(), input, &limits);
+    assert_eq!(result.get_warnings().len(), 1);
+    let warning = &result.get_warnings()[0];
+    assert!(matches!(
+        warning.get_reason(),
+        WarningReason::TooManyGroups { max_groups: 2 }
+    ));
+    assert_eq!(warning.get_line_no(), 7);
+    assert_eq!(warning.get_line_text(), "User-agent: c");
+    let robots_txt = result.get_result();
+    assert_eq!(robots_txt.get_groups().len(), 2);
+    assert!(!robots_txt.can_fetch("b", &url("/b")));
+    assert!(robots_txt.can_fetch("c", &url("/c")));
+
+    // The groups that are split from a record with many user agents count against the limit.
+    let input = "User-agent: a\nUser-agent: b\nUser-agent: c\nDisallow: /x\n\nUser-agent: a\nAllow: /y\n\nUser-agent: b\nAllow: /z";
+    let result = parse_robots_txt_with_options(origin(), input, &rfc9309_options(limits));
+    let warning = result.get_warnings().last().unwrap();
+    assert!(matches!(
+        warning.get_reason(),
+        WarningReason::TooManyGroups { max_groups: 2 }
+    ));
+    assert_eq!(warning.get_line_no(), 3);
+    assert_eq!(result.get_result().get_groups().len(), 2);
+}
+
+#[test]
+fn test_max_rules() {
+    let limits = ParserLimits::default().with_max_rules(3);
+    let input = "User-agent: a\nDisallow: /a\nDisallow: /b\n\nUser-agent: b\nDisallow: /a\nDisallow: /b";
+    let result = parse_robots_txt_with_limits(origin(), input, &limits);
+    assert_eq!(result.get_warnings().len(), 1);
+    let warning = &result.get_warnings()[0];
+    assert!(matches!(
+        warning.get_reason(),
+        WarningReason::TooManyRulesInTotal { max_rules: 3 }
+    ));
+    assert_eq!(warning.get_line_no(), 7);
+    let robots_txt = result.get_result();
+    assert!(!robots_txt.can_fetch("b", &url("/a")));
+    assert!(robots_txt.can_fetch("b", &url("/b")));
+
+    // The rules that are copied into the merged groups count against the limit.
+    let input = format!(
+        "User-agent: a\nUser-agent: b\nUser-agent: c\n{}\nUser-agent: b\nAllow: /y\n\nUser-agent: c\nAllow: /z",
+        "Disallow: /x\n".repeat(4)
+    );
+    let limits = ParserLimits::default().with_max_rules(10);
+    let result = parse_robots_txt_with_options(origin(), &input, &rfc9309_options(limits));
+    let too_many_rules: Vec<_> = result
+        .get_warnings()
+        .iter()
+        .filter(|warning| matches!(warning.get_reason(), WarningReason::TooManyRulesInTotal { .. }))
+        .collect();
+    assert_eq!(too_many_rules.len(), 1);
+    assert_eq!(too_many_rules[0].get_line_no(), 5);
+    let rules: usize = result
+        .get_result()
+        .get_groups()
+        .iter()
+        .map(|group| group.get_rules().len())
+        .sum();
+    assert_eq!(rules, 10);
+}
+
+#[test]
+fn test_max_pattern_length() {
+    let limits = ParserLimits::default().with_max_pattern_length(4);
+    let input = "User-agent: *\nDisallow: /abc\nAllow: /abcd";
+    let result = parse_robots_txt_with_limits(origin(), input, &limits);
+    let reasons: Vec<&WarningReason> = result
+        .get_warnings()
+        .iter()
+        .map(|warning| warning.get_reason())
+        .collect();
+    assert_eq!(reasons.len(), 1);
+    assert!(matches!(reasons[0], WarningReason::PatternTooLong { max_length: 4 }));
+    assert_eq!(result.get_warnings()[0].get_line_no(), 3);
+    let robots_txt = result.get_result();
+    assert!(!robots_txt.can_fetch("*", &url("/abcd")));
+}
+
+#[test]
+fn test_max_wildcards_per_pattern() {
+    let limits = ParserLimits::default().with_max_wildcards_per_pattern(2);
+    let input = "User-agent: *\nDisallow: /*a*\nDisallow: /*b*c*";
+    let result = parse_robots_txt_with_limits(origin(), input, &limits);
+    assert_eq!(result.get_warnings().len(), 1);
+    let warning = &result.get_warnings()[0];
+    assert!(matches!(
+        warning.get_reason(),
+        WarningReason::TooManyWildcards { max_wildcards: 2 }
+    ));
+    assert_eq!(warning.get_line_no(), 3);
+    let robots_txt = result.get_result();
+    assert!(!robots_txt.can_fetch("*", &url("/xax")));
+    assert!(robots_txt.can_fetch("*", &url("/xbxcx")));
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use common::origin;
+use robotparser::model::{EvaluationMode, FetchMetadata, FetchPolicy, ProductTokenMatcher};
+use robotparser::parser::{parse_fetched_robots_txt_with_metadata, parse_fetched_robots_txt_with_options};
+use robotparser::parser::{parse_robots_txt, parse_robots_txt_with_options, ExtensionDirective, ParserLimits};
+use robotparser::parser::{ParserOptions, WarningReason};
+use robotparser::service::RobotsTxtService;
+use std::time::Duration;
+use url::Url;
+
+fn url(path: &str) -> Url {
+    Url::parse("https://example.com").unwrap().join(path).unwrap()
+}
+
+#[test]
+fn test_default_options() {
+    let options = ParserOptions::default();
+    assert!(!options.is_lenient());
+    assert!(options.is_directive_enabled(ExtensionDirective::CrawlDelay));
+    assert_eq!(options.get_evaluation_mode(), EvaluationMode::Legacy);
+    assert_eq!(
+        options.get_limits().get_max_lines(),
+        ParserLimits::default().get_max_lines()
+    );
+    let input = "User-agent: *\nDisallow private\nDisallow: page\nCrawl-delay: 5";
+    let default_result = parse_robots_txt(origin(), input);
+    let options_result = parse_robots_txt_with_options(origin(), input, &options);
+    assert_eq!(default_result.get_warnings().len(), 2);
+    assert_eq!(options_result.get_warnings().len(), 2);
+    assert!(options_result.get_result().can_fetch("*", &url("/private")));
+}
+
+#[test]
+fn test_lenient_options() {
+    let options = ParserOptions::default().with_lenient(true);
+    let input = "User-agent: *\nDisallow private\nDisallow: page\nAllow  /private/public # comment";
+    let result = parse_robots_txt_with_options(origin(), input, &options);
+    assert!(result.get_warnings().is_empty());
+    let robots_txt = result.get_result();
+    assert!(!robots_txt.can_fetch("*", &url("/private")));
+    assert!(!robots_txt.can_fetch("*", &url("/page")));
+    assert!(robots_txt.can_fetch("*", &url("/private/public")));
+    let result = parse_robots_txt_with_options(origin(), "User-agent: *\nDisallow", &options);
+    assert!(matches!(
+        result.get_warnings()[0].get_reason(),
+        WarningReason::InvalidDirectiveFormat
+    ));
+}
+
+#[test]
+fn test_disabled_directives() {
+    let options = ParserOptions::default()
+        .with_directive(ExtensionDirective::CrawlDelay, false)
+        .with_directive(ExtensionDirective::Sitemap, false);
+    let input = "User-agent: *\nCrawl-delay: 5\nDisallow: /a\n\nSitemap: https://example.com/sitemap.xml";
+    let result = parse_robots_txt_with_options(origin(), input, &options);
+    let reasons: Vec<&WarningReason> = result
+        .get_warnings()
+        .iter()
+        .map(|warning| warning.get_reason())
+        .collect();
+    assert_eq!(reasons.len(), 2);
+    assert!(matches!(reasons[0], WarningReason::UnsupportedDirectiveKey(ref key) if key == "crawl-delay"));
+    assert!(matches!(reasons[1], WarningReason::UnsupportedDirectiveKey(ref key) if key == "sitemap"));
+    let robots_txt = result.get_result();
+    assert_eq!(robots_txt.get_crawl_delay("*"), None);
+    assert!(robots_txt.get_sitemaps().is_empty());
+    let options = options.with_directive(ExtensionDirective::CrawlDelay, true);
+    assert!(options.is_directive_enabled(ExtensionDirective::CrawlDelay));
+    assert!(!options.is_directive_enabled(ExtensionDirective::Sitemap));
+    let robots_txt = parse_robots_txt_with_options(origin(), input, &options).get_result();
+    assert_eq!(robots_txt.get_crawl_delay("*"), Some(Duration::from_secs(5)));
+}
+
+#[test]
+fn test_matching_options() {
+    let options = ParserOptions::default()
+        .with_evaluation_mode(EvaluationMode::Rfc9309)
+        .with_user_agent_matcher(ProductTokenMatcher);
+    let input = "User-agent: bot\nDisallow: /\n\nUser-agent: *\nAllow: /";
+    let robots_txt = parse_robots_txt_with_options(origin(), input, &options).get_result();
+    assert_eq!(robots_txt.get_evaluation_mode(), EvaluationMode::Rfc9309);
+    assert!(!robots_txt.can_fetch("Bot/1.0", &url("/page")));
+    assert!(robots_txt.can_fetch("superbot", &url("/page")));
+    let robots_txt = parse_robots_txt(origin(), input).get_result();
+    assert!(!robots_txt.can_fetch("superbot", &url("/page")));
+}
+
+#[test]
+fn test_fetched_robots_txt_options() {
+    let options = ParserOptions::default().with_lenient(true);
+    let input = "User-agent: *\nDisallow private";
+    let robots_txt = parse_fetched_robots_txt_with_options(origin(), 200, input, &options).get_result();
+    assert!(!robots_txt.can_fetch("*", &url("/private")));
+    let policy = FetchPolicy::rfc9309().with_parser_options(options);
+    assert!(policy.get_parser_options().is_lenient());
+    let robots_txt =
+        parse_fetched_robots_txt_with_metadata(origin(), FetchMetadata::new(200), input, &policy).get_result();
+    assert!(!robots_txt.can_fetch("*", &url("/private")));
+    let robots_txt =
+        parse_fetched_robots_txt_with_metadata(origin(), FetchMetadata::new(200), input, &FetchPolicy::rfc9309())
+            .get_result();
+    assert!(robots_txt.can_fetch("*", &url("/private")));
+}
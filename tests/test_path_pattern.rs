@@ -0,0 +1,90 @@
+mod common;
+
+use common::{origin, reference};
+use robotparser::parser::{parse_robots_txt, parse_robots_txt_with_limits, ParserLimits};
+use robotparser::service::RobotsTxtService;
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// Checks that the `Disallow` rule with the pattern applies to the path.
+fn disallows(pattern: &str, path: &str) -> bool {
+    let origin = origin();
+    let robots_txt = parse_robots_txt(origin, &format!("User-agent: *\nDisallow: {}", pattern)).get_result();
+    !robots_txt.can_fetch("*", &Url::parse(&format!("https://example.com{}", path)).unwrap())
+}
+
+/// Returns all strings of the alphabet whose length is between `min_len` and `max_len`.
+fn strings(alphabet: &[char], min_len: usize, max_len: usize) -> Vec<String> {
+    let mut result = vec![String::new()];
+    let mut last = vec![String::new()];
+    for _ in 0..max_len {
+        last = last
+            .iter()
+            .flat_map(|prefix| alphabet.iter().map(move |c| format!("{}{}", prefix, c)))
+            .collect();
+        result.extend(last.iter().cloned());
+    }
+    result.retain(|item| item.chars().count() >= min_len);
+    result
+}
+
+#[test]
+fn test_backtracking_wildcards() {
+    assert!(disallows("/a*b$", "/abxb"));
+    assert!(disallows("/*.php$", "/x.php.php"));
+    assert!(!disallows("/*.php$", "/x.php.html"));
+    assert!(disallows("/*.php", "/x.php.html"));
+    assert!(disallows("/a*b*c$", "/abcbcxbc"));
+    assert!(!disallows("/a*b*c$", "/abcbcxb"));
+    assert!(disallows("/a*aa$", "/aaa"));
+    assert!(!disallows("/a*aa$", "/aa"));
+    assert!(disallows("*/rss$", "/feed/rss"));
+    assert!(!disallows("*/rss$", "/feed/rss/"));
+    assert!(disallows("/a$*", "/a"));
+    assert!(!disallows("/a$b", "/a"));
+    assert!(!disallows("/a$b", "/a$b"));
+}
+
+#[test]
+fn test_wildcards_against_reference() {
+    let patterns = strings(&['/', 'a', 'b', '*', '$'], 1, 5);
+    let paths: Vec<String> = strings(&['/', 'a', 'b'], 0, 5)
+        .into_iter()
+        .map(|path| format!("/{}", path))
+        .collect();
+    let origin = origin();
+    let urls: Vec<Url> = paths
+        .iter()
+        .map(|path| Url::parse(&format!("https://example.com{}", path)).unwrap())
+        .collect();
+    for pattern in patterns
+        .iter()
+        .filter(|pattern| pattern.starts_with('/') || pattern.starts_with('*'))
+    {
+        let robots_txt =
+            parse_robots_txt(origin.clone(), &format!("User-agent: *\nDisallow: {}", pattern)).get_result();
+        let regex = reference(pattern);
+        for (path, url) in paths.iter().zip(urls.iter()) {
+            assert_eq!(
+                !robots_txt.can_fetch("*", url),
+                regex.is_match(path),
+                "pattern `{}`, path `{}`",
+                pattern,
+                path
+            );
+        }
+    }
+}
+
+#[test]
+fn test_wildcards_linear_time() {
+    let pattern = format!("/{}b$", "*a".repeat(1000));
+    let path = format!("/{}", "a".repeat(100_000));
+    let origin = origin();
+    let input = format!("User-agent: *\nDisallow: {}", pattern);
+    let robots_txt = parse_robots_txt_with_limits(origin, &input, &ParserLimits::unlimited()).get_result();
+    let started_at = Instant::now();
+    assert!(robots_txt.can_fetch("*", &Url::parse(&format!("https://example.com{}", path)).unwrap()));
+    assert!(!robots_txt.can_fetch("*", &Url::parse(&format!("https://example.com{}b", path)).unwrap()));
+    assert!(started_at.elapsed() < Duration::from_secs(5));
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use common::origin;
+use robotparser::parser::parse_robots_txt;
+use robotparser::service::RobotsTxtService;
+use url::Url;
+
+fn can_fetch(input: &str, path: &str) -> bool {
+    let robots_txt = parse_robots_txt(origin(), input).get_result();
+    robots_txt.can_fetch("*", &Url::parse(&format!("https://example.com{}", path)).unwrap())
+}
+
+#[test]
+fn test_reserved_characters_stay_encoded() {
+    let input = "User-agent: *\nDisallow: /a%2Fb\nDisallow: /c/d";
+    assert!(!can_fetch(input, "/a%2Fb"));
+    assert!(!can_fetch(input, "/a%2fb"));
+    assert!(can_fetch(input, "/a/b"));
+    assert!(!can_fetch(input, "/c/d"));
+    assert!(can_fetch(input, "/c%2Fd"));
+    let input = "User-agent: *\nDisallow: /search?q=%3D";
+    assert!(!can_fetch(input, "/search?q=%3d"));
+    assert!(can_fetch(input, "/search?q=="));
+}
+
+#[test]
+fn test_unreserved_characters_are_decoded() {
+    let input = "User-agent: *\nDisallow: /%7ejoe/\nDisallow: /caf%C3%A9\nDisallow: /%41bc";
+    assert!(!can_fetch(input, "/~joe/index.html"));
+    assert!(!can_fetch(input, "/%7Ejoe/index.html"));
+    assert!(!can_fetch(input, "/caf%c3%a9"));
+    assert!(!can_fetch(input, "/café"));
+    assert!(!can_fetch(input, "/Abc"));
+    assert!(!can_fetch(input, "/%41%62%63"));
+    assert!(can_fetch(input, "/abc"));
+}
+
+#[test]
+fn test_non_ascii_pattern_is_encoded() {
+    let input = "User-agent: *\nDisallow: /café\nDisallow: /a b";
+    assert!(!can_fetch(input, "/caf%C3%A9"));
+    assert!(!can_fetch(input, "/café/menu"));
+    assert!(!can_fetch(input, "/a%20b"));
+    assert!(can_fetch(input, "/cafe"));
+}
+
+#[test]
+fn test_non_utf8_paths_match_byte_for_byte() {
+    let input = "User-agent: *\nDisallow: /%FF%FE\nDisallow: /x%e9";
+    assert!(!can_fetch(input, "/%ff%fe"));
+    assert!(can_fetch(input, "/%FF%FD"));
+    assert!(can_fetch(input, "/\u{fffd}\u{fffd}"));
+    assert!(!can_fetch(input, "/x%E9y"));
+    assert!(can_fetch(input, "/x%C3%A9"));
+}
+
+#[test]
+fn test_invalid_escapes() {
+    let input = "User-agent: *\nDisallow: /100%\nDisallow: /%zz";
+    assert!(!can_fetch(input, "/100%25"));
+    assert!(!can_fetch(input, "/100%"));
+    assert!(!can_fetch(input, "/%25zz"));
+}
@@ -0,0 +1,252 @@
+mod common;
+
+use common::{policy, TestResponse, TestServer};
+use robotparser::http::RobotsTxtClient;
+use robotparser::model::{CrossOriginRedirects, FetchPolicy, FetchStatus, RedirectPolicy};
+use robotparser::service::RobotsTxtService;
+use tokio::runtime::Runtime;
+use url::Url;
+
+const DOC: &str = "User-agent: *\nDisallow: /private";
+
+/// Returns the client that leaves the redirects to the fetcher.
+fn client() -> reqwest::blocking::Client {
+    reqwest::blocking::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap()
+}
+
+fn redirect(location: &str) -> TestResponse {
+    TestResponse::new(301, "").with_header("Location", location)
+}
+
+#[test]
+fn test_redirect_policy() {
+    let policy = RedirectPolicy::default();
+    assert_eq!(policy.get_max_redirects(), 0);
+    let policy = FetchPolicy::rfc9309();
+    assert_eq!(policy.get_redirect_policy().get_max_redirects(), 5);
+    assert_eq!(
+        policy.get_redirect_policy().get_cross_origin(),
+        CrossOriginRedirects::Follow
+    );
+    assert_eq!(FetchPolicy::legacy().get_redirect_policy().get_max_redirects(), 0);
+
+    let origin = Url::parse("http://example.com/robots.txt").unwrap().origin();
+    let https = Url::parse("https://example.com/robots.txt").unwrap();
+    let other = Url::parse("http://other.com/robots.txt").unwrap();
+    let same = Url::parse("http://example.com/other/robots.txt").unwrap();
+    let policy = RedirectPolicy::rfc9309();
+    assert!(policy.is_allowed(&origin, &https));
+    assert!(policy.is_allowed(&origin, &other));
+    let policy = RedirectPolicy::rfc9309().with_cross_origin(CrossOriginRedirects::SameHost);
+    assert!(policy.is_allowed(&origin, &https));
+    assert!(!policy.is_allowed(&origin, &other));
+    let policy = RedirectPolicy::rfc9309().with_cross_origin(CrossOriginRedirects::Deny);
+    assert!(!policy.is_allowed(&origin, &https));
+    assert!(policy.is_allowed(&origin, &same));
+    assert!(!RedirectPolicy::none().is_allowed(&origin, &same));
+}
+
+#[test]
+fn test_redirect_chain_is_recorded() {
+    let server = TestServer::start(vec![
+        redirect("/a/robots.txt"),
+        redirect("/b/robots.txt"),
+        TestResponse::new(200, DOC),
+    ]);
+    let robots_txt = client()
+        .fetch_robots_txt_with_policy(server.origin(), &policy())
+        .unwrap()
+        .get_result();
+    assert_eq!(robots_txt.get_status(), FetchStatus::Fetched);
+    let metadata = robots_txt.get_metadata();
+    assert_eq!(
+        metadata.get_redirects(),
+        &[server.url("/robots.txt"), server.url("/a/robots.txt")]
+    );
+    assert_eq!(metadata.get_final_url(), Some(&server.url("/b/robots.txt")));
+    assert!(!robots_txt.can_fetch("*", &server.url("/private")));
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert!(requests[2][0].starts_with("GET /b/robots.txt "));
+}
+
+#[test]
+fn test_redirect_limit() {
+    let mut responses: Vec<TestResponse> = (1..=5).map(|hop| redirect(&format!("/{}/robots.txt", hop))).collect();
+    responses.push(TestResponse::new(200, DOC));
+    let server = TestServer::start(responses);
+    let robots_txt = client()
+        .fetch_robots_txt_with_policy(server.origin(), &policy())
+        .unwrap()
+        .get_result();
+    assert_eq!(robots_txt.get_status(), FetchStatus::Fetched);
+    assert_eq!(robots_txt.get_metadata().get_redirects().len(), 5);
+
+    let responses = (1..=6).map(|hop| redirect(&format!("/{}/robots.txt", hop))).collect();
+    let server = TestServer::start(responses);
+    let robots_txt = client()
+        .fetch_robots_txt_with_policy(server.origin(), &policy())
+        .unwrap()
+        .get_result();
+    assert_eq!(robots_txt.get_status(), FetchStatus::Unreachable);
+    assert_eq!(robots_txt.get_status_code(), Some(301));
+    assert_eq!(robots_txt.get_metadata().get_redirects().len(), 5);
+    assert_eq!(server.requests().len(), 6);
+    assert!(!robots_txt.can_fetch("*", &server.url("/page")));
+}
+
+#[test]
+fn test_redirect_loop() {
+    let server = TestServer::start(vec![redirect("/a/robots.txt"), redirect("/robots.txt")]);
+    let robots_txt = client()
+        .fetch_robots_txt_with_policy(server.origin(), &policy())
+        .unwrap()
+        .get_result();
+    assert_eq!(robots_txt.get_status(), FetchStatus::Unreachable);
+    assert_eq!(server.requests().len(), 2);
+
+    let server = TestServer::start(vec![redirect("/robots.txt")]);
+    let robots_txt = client()
+        .fetch_robots_txt_with_policy(server.origin(), &policy())
+        .unwrap()
+        .get_result();
+    assert_eq!(robots_txt.get_status(), FetchStatus::Unreachable);
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn test_cross_origin_redirects() {
+    let target = TestServer::start(vec![TestResponse::new(200, DOC)]);
+    let port = target.url("/").port().unwrap();
+    let same_host = format!("http://127.0.0.1:{}/robots.txt", port);
+    let other_host = format!("http://localhost:{}/robots.txt", port);
+    let fetch = |location: &str, cross_origin| {
+        let server = TestServer::start(vec![redirect(location)]);
+        let redirect_policy = RedirectPolicy::rfc9309().with_cross_origin(cross_origin);
+        client()
+            .fetch_robots_txt_with_policy(server.origin(), &policy().with_redirect_policy(redirect_policy))
+            .unwrap()
+            .get_result()
+    };
+    assert_eq!(
+        fetch(&other_host, CrossOriginRedirects::Follow).get_status(),
+        FetchStatus::Fetched
+    );
+    assert_eq!(
+        fetch(&same_host, CrossOriginRedirects::SameHost).get_status(),
+        FetchStatus::Fetched
+    );
+    let robots_txt = fetch(&other_host, CrossOriginRedirects::SameHost);
+    assert_eq!(robots_txt.get_status_code(), Some(301));
+    assert_eq!(robots_txt.get_status(), FetchStatus::Failed);
+    assert_eq!(robots_txt.get_metadata().get_redirects(), &[] as &[Url]);
+    let robots_txt = fetch(&same_host, CrossOriginRedirects::Deny);
+    assert_eq!(robots_txt.get_status_code(), Some(301));
+    assert_eq!(target.requests().len(), 2);
+}
+
+#[test]
+fn test_legacy_policy_does_not_follow_redirects() {
+    let server = TestServer::start(vec![redirect("/a/robots.txt"), TestResponse::new(200, DOC)]);
+    let robots_txt = client()
+        .fetch_robots_txt_with_policy(server.origin(), &FetchPolicy::legacy())
+        .unwrap()
+        .get_result();
+    assert_eq!(robots_txt.get_status_code(), Some(301));
+    assert!(robots_txt.can_fetch("*", &server.url("/private")));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn test_redirects_followed_by_default_client() {
+    let client = reqwest::blocking::Client::new();
+    let target = TestServer::start(vec![TestResponse::new(200, DOC)]);
+    let other_host = format!("http://localhost:{}/robots.txt", target.url("/").port().unwrap());
+
+    // The redirect that the policy allows is accepted.
+    let server = TestServer::start(vec![redirect("/a/robots.txt"), TestResponse::new(200, DOC)]);
+    let robots_txt = client
+        .fetch_robots_txt_with_policy(server.origin(), &policy())
+        .unwrap()
+        .get_result();
+    assert_eq!(robots_txt.get_status(), FetchStatus::Fetched);
+    assert_eq!(robots_txt.get_metadata().get_redirects(), &[server.url("/robots.txt")]);
+    assert_eq!(
+        robots_txt.get_metadata().get_final_url(),
+        Some(&server.url("/a/robots.txt"))
+    );
+
+    // The default policy accepts the redirects of the client, like the previous versions.
+    let server = TestServer::start(vec![redirect("/a/robots.txt"), TestResponse::new(200, DOC)]);
+    let robots_txt = client.fetch_robots_txt(server.origin()).unwrap().get_result();
+    assert_eq!(robots_txt.get_status(), FetchStatus::Fetched);
+    assert!(robots_txt.can_fetch("*", &server.url("/page")));
+    assert!(!robots_txt.can_fetch("*", &server.url("/private")));
+
+    // The policy that is set explicitly and forbids the redirect fails closed.
+    let server = TestServer::start(vec![redirect(&other_host)]);
+    let redirect_policy = RedirectPolicy::none().with_cross_origin(CrossOriginRedirects::Deny);
+    let robots_txt = client
+        .fetch_robots_txt_with_policy(server.origin(), &policy().with_redirect_policy(redirect_policy))
+        .unwrap()
+        .get_result();
+    assert_eq!(robots_txt.get_status(), FetchStatus::Unreachable);
+    assert!(!robots_txt.can_fetch("*", &server.url("/page")));
+
+    // The redirect to another origin is not allowed by the cross-origin rules.
+    let server = TestServer::start(vec![redirect(&other_host)]);
+    let redirect_policy = RedirectPolicy::rfc9309().with_cross_origin(CrossOriginRedirects::SameHost);
+    let robots_txt = client
+        .fetch_robots_txt_with_policy(server.origin(), &policy().with_redirect_policy(redirect_policy))
+        .unwrap()
+        .get_result();
+    assert_eq!(robots_txt.get_status(), FetchStatus::Unreachable);
+    assert!(!robots_txt.can_fetch("*", &server.url("/page")));
+
+    let async_client = reqwest::Client::new();
+    let runtime = Runtime::new().unwrap();
+    let server = TestServer::start(vec![redirect("/a/robots.txt"), TestResponse::new(200, DOC)]);
+    let robots_txt = runtime
+        .block_on(async_client.fetch_robots_txt(server.origin()).unwrap())
+        .unwrap()
+        .get_result();
+    assert_eq!(robots_txt.get_status(), FetchStatus::Fetched);
+    assert!(!robots_txt.can_fetch("*", &server.url("/private")));
+    let server = TestServer::start(vec![redirect(&other_host)]);
+    let redirect_policy = RedirectPolicy::rfc9309().with_cross_origin(CrossOriginRedirects::Deny);
+    let robots_txt = runtime
+        .block_on(
+            async_client
+                .fetch_robots_txt_with_policy(server.origin(), &policy().with_redirect_policy(redirect_policy))
+                .unwrap(),
+        )
+        .unwrap()
+        .get_result();
+    assert_eq!(robots_txt.get_status(), FetchStatus::Unreachable);
+}
+
+#[test]
+fn test_async_redirects() {
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+    let runtime = Runtime::new().unwrap();
+    let server = TestServer::start(vec![redirect("/a/robots.txt"), TestResponse::new(200, DOC)]);
+    let robots_txt = runtime
+        .block_on(client.fetch_robots_txt_with_policy(server.origin(), &policy()).unwrap())
+        .unwrap()
+        .get_result();
+    assert_eq!(robots_txt.get_status(), FetchStatus::Fetched);
+    assert_eq!(robots_txt.get_metadata().get_redirects(), &[server.url("/robots.txt")]);
+
+    let server = TestServer::start(vec![redirect("/a/robots.txt"), redirect("/robots.txt")]);
+    let robots_txt = runtime
+        .block_on(client.fetch_robots_txt_with_policy(server.origin(), &policy()).unwrap())
+        .unwrap()
+        .get_result();
+    assert_eq!(robots_txt.get_status(), FetchStatus::Unreachable);
+}
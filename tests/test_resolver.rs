@@ -0,0 +1,197 @@
+mod common;
+
+use common::{policy, TestResponse, TestServer};
+use futures::future::join_all;
+use reqwest::Client;
+use robotparser::cache::{MemoryRobotsCache, RobotsCache};
+use robotparser::http::RobotsResolver;
+use robotparser::model::Clock;
+use robotparser::service::RobotsTxtService;
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+use tokio::runtime::Runtime;
+use url::Url;
+
+const DOC: &str = "User-agent: *\nDisallow: /private";
+
+fn resolver() -> RobotsResolver<Client> {
+    RobotsResolver::new(Client::new()).with_policy(policy())
+}
+
+#[test]
+fn test_resolver_coalesces_concurrent_fetches() {
+    let server = TestServer::start(vec![TestResponse::new(200, DOC)]);
+    let resolver = resolver();
+    let runtime = Runtime::new().unwrap();
+    let urls: Vec<Url> = (0..500)
+        .map(|index| {
+            if index % 2 == 0 {
+                server.url(&format!("/private/{}", index))
+            } else {
+                server.url(&format!("/public/{}", index))
+            }
+        })
+        .collect();
+    let results = runtime.block_on(join_all(urls.iter().map(|url| resolver.can_fetch("*", url))));
+    for (index, result) in results.into_iter().enumerate() {
+        assert_eq!(result.unwrap(), index % 2 == 1);
+    }
+    assert_eq!(server.requests().len(), 1);
+    let robots_txts = runtime.block_on(join_all(urls.iter().take(2).map(|url| resolver.resolve(url))));
+    let first = robots_txts[0].as_ref().unwrap();
+    let second = robots_txts[1].as_ref().unwrap();
+    assert!(Arc::ptr_eq(first, second));
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn test_resolver_shared_cache() {
+    let server = TestServer::start(vec![TestResponse::new(200, DOC)]);
+    let cache = Arc::new(MemoryRobotsCache::new(10));
+    let resolver = resolver().with_cache(cache.clone());
+    let runtime = Runtime::new().unwrap();
+    let url = server.url("/private");
+    assert!(!runtime.block_on(resolver.can_fetch("*", &url)).unwrap());
+    assert!(cache.get(&url).is_some());
+    cache.clear();
+    assert!(!runtime.block_on(resolver.can_fetch("*", &url)).unwrap());
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn test_resolver_does_not_cache_errors() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = Url::parse(&format!("http://{}/page", listener.local_addr().unwrap())).unwrap();
+    drop(listener);
+    let resolver = RobotsResolver::new(Client::new());
+    let runtime = Runtime::new().unwrap();
+    let results = runtime.block_on(join_all((0..10).map(|_| resolver.resolve(&url))));
+    assert!(results.iter().all(|result| result.is_err()));
+    assert!(resolver.get_cache().get(&url).is_none());
+    assert!(runtime.block_on(resolver.resolve(&url)).is_err());
+}
+
+/// Clock that starts at the current time and is moved forward manually.
+/// The time is kept in nanoseconds, so that it never runs behind the fetch time of the robots.txt files.
+#[derive(Clone)]
+struct ManualClock(Arc<AtomicU64>);
+
+impl ManualClock {
+    fn new() -> ManualClock {
+        let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap();
+        ManualClock(Arc::new(AtomicU64::new(now.as_nanos() as u64)))
+    }
+
+    fn advance(&self, duration: Duration) {
+        self.0.fetch_add(duration.as_nanos() as u64, Ordering::SeqCst);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_nanos(self.0.load(Ordering::SeqCst))
+    }
+}
+
+fn wait_until(condition: impl Fn() -> bool) {
+    for _ in 0..500 {
+        if condition() {
+            return;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    panic!("condition was not met");
+}
+
+fn stale_resolver(runtime: &Runtime, clock: &ManualClock) -> (RobotsResolver<Client>, Arc<MemoryRobotsCache>) {
+    let cache = Arc::new(
+        MemoryRobotsCache::new(10)
+            .with_max_stale(Duration::from_secs(3600))
+            .with_clock(clock.clone()),
+    );
+    let handle = runtime.handle().clone();
+    let resolver = resolver()
+        .with_cache(cache.clone())
+        .with_clock(clock.clone())
+        .with_stale_while_revalidate(move |refresh| {
+            handle.spawn(refresh);
+        });
+    (resolver, cache)
+}
+
+#[test]
+fn test_resolver_stale_while_revalidate() {
+    let server = TestServer::start(vec![
+        TestResponse::new(200, DOC).with_header("Cache-Control", "max-age=60"),
+        TestResponse::new(200, "User-agent: *\nDisallow: /public").with_header("Cache-Control", "max-age=3600"),
+    ]);
+    let runtime = Runtime::new().unwrap();
+    let clock = ManualClock::new();
+    let (resolver, cache) = stale_resolver(&runtime, &clock);
+    let url = server.url("/private");
+    assert!(!runtime.block_on(resolver.can_fetch("*", &url)).unwrap());
+    clock.advance(Duration::from_secs(61));
+    assert!(cache.get(&url).is_none());
+    assert!(!runtime.block_on(resolver.can_fetch("*", &url)).unwrap());
+    wait_until(|| cache.get(&url).is_some());
+    assert!(runtime.block_on(resolver.can_fetch("*", &url)).unwrap());
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn test_resolver_stale_while_revalidate_default_cache() {
+    let server = TestServer::start(vec![
+        TestResponse::new(200, DOC).with_header("Cache-Control", "no-cache"),
+        TestResponse::new(200, "User-agent: *\nDisallow: /public").with_header("Cache-Control", "max-age=3600"),
+    ]);
+    let runtime = Runtime::new().unwrap();
+    let handle = runtime.handle().clone();
+    let resolver = resolver().with_stale_while_revalidate(move |refresh| {
+        handle.spawn(refresh);
+    });
+    let url = server.url("/private");
+    assert!(!runtime.block_on(resolver.can_fetch("*", &url)).unwrap());
+    assert!(resolver.get_cache().get_stale_by_origin(&url.origin()).is_some());
+    // The expired file is served while it is refreshed in the background.
+    assert!(!runtime.block_on(resolver.can_fetch("*", &url)).unwrap());
+    wait_until(|| resolver.get_cache().get_by_origin(&url.origin()).is_some());
+    assert!(runtime.block_on(resolver.can_fetch("*", &url)).unwrap());
+    assert_eq!(server.requests().len(), 2);
+}
+
+#[test]
+fn test_resolver_stale_refresh_failure() {
+    let server = TestServer::start(vec![
+        TestResponse::new(200, DOC).with_header("Cache-Control", "max-age=60"),
+        TestResponse::new(503, ""),
+    ]);
+    let runtime = Runtime::new().unwrap();
+    let clock = ManualClock::new();
+    let (resolver, cache) = stale_resolver(&runtime, &clock);
+    let url = server.url("/private");
+    let public_url = server.url("/public");
+    assert!(!runtime.block_on(resolver.can_fetch("*", &url)).unwrap());
+    assert_eq!(
+        runtime.block_on(resolver.resolve(&url)).unwrap().get_refresh_failures(),
+        0
+    );
+    clock.advance(Duration::from_secs(61));
+    assert!(!runtime.block_on(resolver.can_fetch("*", &url)).unwrap());
+    let failures = || cache.get_stale_by_origin(&url.origin()).unwrap().get_refresh_failures();
+    wait_until(|| failures() == 1);
+    let robots_txt = runtime.block_on(resolver.resolve(&url)).unwrap();
+    assert_eq!(robots_txt.get_last_refresh_failure(), Some(clock.now()));
+    assert!(!robots_txt.can_fetch("*", &url));
+    assert!(robots_txt.can_fetch("*", &public_url));
+    assert_eq!(server.requests().len(), 2);
+    clock.advance(Duration::from_secs(1));
+    assert!(runtime.block_on(resolver.can_fetch("*", &public_url)).unwrap());
+    wait_until(|| failures() == 2);
+    assert_eq!(server.requests().len(), 3);
+    clock.advance(Duration::from_secs(3600));
+    assert!(!runtime.block_on(resolver.can_fetch("*", &public_url)).unwrap());
+    assert!(runtime.block_on(resolver.resolve(&url)).unwrap().is_unreachable());
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use common::{policy, TestResponse, TestServer};
+use robotparser::http::RobotsTxtClient;
+use robotparser::model::FetchedRobotsTxt;
+use robotparser::service::RobotsTxtService;
+use tokio::runtime::Runtime;
+
+const DOC: &str = "User-agent: *\nDisallow: /private";
+const LAST_MODIFIED: &str = "Wed, 21 Oct 2015 07:28:00 GMT";
+
+fn first_response() -> TestResponse {
+    TestResponse::new(200, DOC)
+        .with_header("ETag", "\"v1\"")
+        .with_header("Last-Modified", LAST_MODIFIED)
+        .with_header("Cache-Control", "max-age=60")
+}
+
+/// Returns the value of the header of the request received by the server.
+fn get_request_header(server: &TestServer, index: usize, name: &str) -> Option<String> {
+    let prefix = format!("{}:", name);
+    server.requests()[index]
+        .iter()
+        .find(|line| line.to_lowercase().starts_with(&prefix))
+        .map(|line| line[prefix.len()..].trim().to_string())
+}
+
+fn fetch_blocking(server: &TestServer) -> (reqwest::blocking::Client, FetchedRobotsTxt) {
+    let client = reqwest::blocking::Client::new();
+    let robots_txt = client
+        .fetch_robots_txt_with_policy(server.origin(), &policy())
+        .unwrap()
+        .get_result();
+    (client, robots_txt)
+}
+
+#[test]
+fn test_revalidation_not_modified() {
+    let server = TestServer::start(vec![
+        first_response(),
+        TestResponse::new(304, "").with_header("Cache-Control", "max-age=120"),
+    ]);
+    let (client, previous) = fetch_blocking(&server);
+    assert_eq!(previous.get_etag(), Some("\"v1\""));
+    assert_eq!(previous.get_last_modified(), Some(LAST_MODIFIED));
+    assert_eq!(get_request_header(&server, 0, "if-none-match"), None);
+    let robots_txt = client
+        .revalidate_robots_txt_with_policy(server.origin(), &previous, &policy())
+        .unwrap()
+        .get_result();
+    assert_eq!(
+        get_request_header(&server, 1, "if-none-match").as_deref(),
+        Some("\"v1\"")
+    );
+    assert_eq!(
+        get_request_header(&server, 1, "if-modified-since").as_deref(),
+        Some(LAST_MODIFIED)
+    );
+    assert_eq!(robots_txt.get_metadata().get_status_code(), Some(200));
+    assert_eq!(robots_txt.get_etag(), Some("\"v1\""));
+    assert_eq!(robots_txt.get_cache_lifetime().as_secs(), 120);
+    assert!(robots_txt.get_fetched_at() >= previous.get_fetched_at());
+    assert!(!robots_txt.can_fetch("*", &server.url("/private")));
+    assert!(robots_txt.can_fetch("*", &server.url("/public")));
+}
+
+#[test]
+fn test_revalidation_modified() {
+    let server = TestServer::start(vec![
+        first_response(),
+        TestResponse::new(200, "User-agent: *\nDisallow: /public").with_header("ETag", "\"v2\""),
+    ]);
+    let (client, previous) = fetch_blocking(&server);
+    let robots_txt = client
+        .revalidate_robots_txt_with_policy(server.origin(), &previous, &policy())
+        .unwrap()
+        .get_result();
+    assert_eq!(robots_txt.get_etag(), Some("\"v2\""));
+    assert_eq!(robots_txt.get_last_modified(), None);
+    assert!(robots_txt.can_fetch("*", &server.url("/private")));
+    assert!(!robots_txt.can_fetch("*", &server.url("/public")));
+}
+
+#[test]
+fn test_revalidation_unreachable() {
+    let server = TestServer::start(vec![first_response(), TestResponse::new(503, "")]);
+    let (client, previous) = fetch_blocking(&server);
+    let robots_txt = client
+        .revalidate_robots_txt_with_policy(server.origin(), &previous, &policy())
+        .unwrap()
+        .get_result();
+    assert!(robots_txt.is_unreachable());
+    assert!(!robots_txt.can_fetch("*", &server.url("/public")));
+    let policy = policy().with_unreachable_timeout(std::time::Duration::from_secs(0));
+    let robots_txt = client
+        .revalidate_robots_txt_with_policy(server.origin(), &previous, &policy)
+        .unwrap()
+        .get_result();
+    assert!(robots_txt.can_fetch("*", &server.url("/public")));
+    assert!(!robots_txt.can_fetch("*", &server.url("/private")));
+}
+
+#[test]
+fn test_revalidation_async() {
+    let server = TestServer::start(vec![first_response(), TestResponse::new(304, "")]);
+    let client = reqwest::Client::new();
+    let runtime = Runtime::new().unwrap();
+    let previous = runtime
+        .block_on(client.fetch_robots_txt_with_policy(server.origin(), &policy()).unwrap())
+        .unwrap()
+        .get_result();
+    let robots_txt = runtime
+        .block_on(client.revalidate_robots_txt(server.origin(), &previous).unwrap())
+        .unwrap()
+        .get_result();
+    assert_eq!(
+        get_request_header(&server, 1, "if-none-match").as_deref(),
+        Some("\"v1\"")
+    );
+    assert_eq!(robots_txt.get_cache_lifetime().as_secs(), 60);
+    assert!(!robots_txt.can_fetch("*", &server.url("/private")));
+}
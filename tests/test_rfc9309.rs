@@ -0,0 +1,195 @@
+use robotparser::model::{EvaluationMode, RobotsTxt};
+use robotparser::parser::{parse_fetched_robots_txt, parse_robots_txt};
+use robotparser::service::RobotsTxtService;
+use std::time::Duration;
+use url::Url;
+
+fn parse(doc: &str, evaluation_mode: EvaluationMode) -> RobotsTxt {
+    let url = Url::parse("https://www.example.com/robots.txt").unwrap();
+    let mut robots_txt = parse_robots_txt(url.origin(), doc).get_result();
+    robots_txt.set_evaluation_mode(evaluation_mode);
+    robots_txt
+}
+
+fn robot_test(doc: &str, evaluation_mode: EvaluationMode, agent: &str, good_urls: &[&str], bad_urls: &[&str]) {
+    let robots_txt = parse(doc, evaluation_mode);
+    for url in good_urls {
+        let url = Url::parse(&format!("https://www.example.com{}", url)).unwrap();
+        assert!(robots_txt.can_fetch(agent, &url), "{} must be allowed", url);
+    }
+    for url in bad_urls {
+        let url = Url::parse(&format!("https://www.example.com{}", url)).unwrap();
+        assert!(!robots_txt.can_fetch(agent, &url), "{} must be disallowed", url);
+    }
+}
+
+#[test]
+fn test_default_evaluation_mode_is_legacy() {
+    let robots_txt = parse_robots_txt(Url::parse("https://www.example.com/").unwrap().origin(), "").get_result();
+    assert_eq!(robots_txt.get_evaluation_mode(), EvaluationMode::Legacy);
+}
+
+#[test]
+fn test_specific_group_does_not_fall_back_to_default_group() {
+    let doc = "\n\
+    User-agent: *\n\
+    Disallow: /private\n\
+    \n\
+    User-agent: foobot\n\
+    Disallow: /tmp\n\
+    ";
+    robot_test(doc, EvaluationMode::Legacy, "foobot", &["/"], &["/tmp", "/private"]);
+    robot_test(doc, EvaluationMode::Rfc9309, "foobot", &["/", "/private"], &["/tmp"]);
+    robot_test(doc, EvaluationMode::Rfc9309, "barbot", &["/", "/tmp"], &["/private"]);
+}
+
+#[test]
+fn test_allow_wins_tie() {
+    let doc = "\n\
+    User-agent: *\n\
+    Disallow: /page\n\
+    Allow: /page\n\
+    ";
+    robot_test(doc, EvaluationMode::Legacy, "foobot", &[], &["/page"]);
+    robot_test(doc, EvaluationMode::Rfc9309, "foobot", &["/page"], &[]);
+}
+
+#[test]
+fn test_longest_match_wins() {
+    let doc = "\n\
+    User-agent: *\n\
+    Allow: /folder/page\n\
+    Disallow: /folder\n\
+    Allow: /folder/page/private-is-public\n\
+    Disallow: /folder/page/private\n\
+    ";
+    for evaluation_mode in [EvaluationMode::Legacy, EvaluationMode::Rfc9309].iter() {
+        robot_test(
+            doc,
+            *evaluation_mode,
+            "foobot",
+            &["/", "/folder/page", "/folder/page/private-is-public"],
+            &["/folder", "/folder/other", "/folder/page/private"],
+        );
+    }
+}
+
+#[test]
+fn test_most_specific_group_wins() {
+    let doc = "\n\
+    User-agent: googlebot\n\
+    Disallow: /\n\
+    \n\
+    User-agent: googlebot-news\n\
+    Allow: /\n\
+    Disallow: /archive\n\
+    ";
+    robot_test(
+        doc,
+        EvaluationMode::Legacy,
+        "googlebot-news",
+        &[],
+        &["/news", "/archive"],
+    );
+    robot_test(
+        doc,
+        EvaluationMode::Rfc9309,
+        "googlebot-news",
+        &["/news"],
+        &["/archive"],
+    );
+    robot_test(doc, EvaluationMode::Rfc9309, "googlebot", &[], &["/news", "/archive"]);
+}
+
+#[test]
+fn test_robots_txt_is_always_allowed() {
+    let doc = "\n\
+    User-agent: *\n\
+    Disallow: /\n\
+    ";
+    robot_test(doc, EvaluationMode::Legacy, "foobot", &[], &["/robots.txt", "/"]);
+    robot_test(
+        doc,
+        EvaluationMode::Rfc9309,
+        "foobot",
+        &["/robots.txt"],
+        &["/", "/robots.txt.bak"],
+    );
+}
+
+#[test]
+fn test_groups_with_same_user_agent_are_combined() {
+    let doc = "\n\
+    User-agent: foobot\n\
+    Disallow: /a\n\
+    \n\
+    User-agent: *\n\
+    Disallow: /c\n\
+    \n\
+    User-agent: foobot\n\
+    Disallow: /b\n\
+    ";
+    robot_test(doc, EvaluationMode::Rfc9309, "foobot", &["/", "/c"], &["/a", "/b"]);
+}
+
+#[test]
+fn test_no_matching_group_allows_everything() {
+    let doc = "\n\
+    User-agent: foobot\n\
+    Disallow: /\n\
+    ";
+    for evaluation_mode in [EvaluationMode::Legacy, EvaluationMode::Rfc9309].iter() {
+        robot_test(doc, *evaluation_mode, "barbot", &["/", "/tmp"], &[]);
+    }
+}
+
+#[test]
+fn test_wildcards() {
+    let doc = "\n\
+    User-agent: *\n\
+    Disallow: /*.gif$\n\
+    Allow: /public/*.gif$\n\
+    Disallow: /*?\n\
+    ";
+    for evaluation_mode in [EvaluationMode::Legacy, EvaluationMode::Rfc9309].iter() {
+        robot_test(
+            doc,
+            *evaluation_mode,
+            "foobot",
+            &["/", "/image.gif.html", "/public/image.gif"],
+            &["/image.gif", "/folder/image.gif", "/search?q=1"],
+        );
+    }
+}
+
+#[test]
+fn test_crawl_delay() {
+    let doc = "\n\
+    User-agent: *\n\
+    Crawl-delay: 5\n\
+    \n\
+    User-agent: foobot\n\
+    Disallow: /tmp\n\
+    ";
+    let url = Url::parse("https://www.example.com/robots.txt").unwrap();
+    let robots_txt = parse(doc, EvaluationMode::Legacy);
+    assert_eq!(robots_txt.get_crawl_delay("foobot"), Some(Duration::from_secs(5)));
+    let robots_txt = parse(doc, EvaluationMode::Rfc9309);
+    assert_eq!(robots_txt.get_crawl_delay("foobot"), None);
+    assert_eq!(robots_txt.get_crawl_delay("barbot"), Some(Duration::from_secs(5)));
+    assert!(robots_txt.can_fetch("foobot", &url));
+}
+
+#[test]
+fn test_fetched_robots_txt_evaluation_mode() {
+    let doc = "\n\
+    User-agent: *\n\
+    Disallow: /page\n\
+    Allow: /page\n\
+    ";
+    let url = Url::parse("https://www.example.com/page").unwrap();
+    let mut robots_txt = parse_fetched_robots_txt(url.origin(), 200, doc).get_result();
+    assert!(!robots_txt.can_fetch("foobot", &url));
+    robots_txt.set_evaluation_mode(EvaluationMode::Rfc9309);
+    assert!(robots_txt.can_fetch("foobot", &url));
+}
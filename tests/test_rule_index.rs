@@ -0,0 +1,146 @@
+mod common;
+
+use common::{origin, reference};
+use regex::Regex;
+use robotparser::model::EvaluationMode;
+use robotparser::parser::{parse_robots_txt, parse_robots_txt_with_options, ParserOptions};
+use robotparser::service::RobotsTxtService;
+use url::Url;
+
+fn url(path: &str) -> Url {
+    Url::parse("https://example.com").unwrap().join(path).unwrap()
+}
+
+fn parse(input: &str, evaluation_mode: EvaluationMode) -> robotparser::model::RobotsTxt {
+    let options = ParserOptions::default().with_evaluation_mode(evaluation_mode);
+    parse_robots_txt_with_options(origin(), input, &options).get_result()
+}
+
+/// Returns the length of the pattern that is used to find the longest match:
+/// repeated wildcards count once, and a pattern that ends with a text has an implicit trailing `*`.
+fn pattern_len(pattern: &str) -> usize {
+    let mut chars: Vec<char> = pattern.chars().collect();
+    chars.dedup_by(|a, b| a == b && (*a == '*' || *a == '$'));
+    match chars.last() {
+        Some('*') | Some('$') => chars.len(),
+        _ => chars.len() + 1,
+    }
+}
+
+/// Returns the decision of the reference implementation that checks every rule.
+fn reference_can_fetch(rules: &[(bool, String, Regex)], path: &str, evaluation_mode: EvaluationMode) -> bool {
+    let mut decision: Option<(usize, bool)> = None;
+    for (allowance, pattern, regex) in rules.iter() {
+        if !regex.is_match(path) {
+            continue;
+        }
+        let len = pattern_len(pattern);
+        let is_better = match decision {
+            None => true,
+            Some((decision_len, decision_allowance)) => {
+                len > decision_len
+                    || (len == decision_len
+                        && evaluation_mode == EvaluationMode::Rfc9309
+                        && *allowance
+                        && !decision_allowance)
+            }
+        };
+        if is_better {
+            decision = Some((len, *allowance));
+        }
+    }
+    decision.map(|(_, allowance)| allowance).unwrap_or(true)
+}
+
+/// Simple deterministic pseudo-random generator.
+struct Random(u64);
+
+impl Random {
+    fn next(&mut self, bound: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((self.0 >> 33) as usize) % bound
+    }
+
+    fn string(&mut self, alphabet: &[char], max_len: usize) -> String {
+        let len = self.next(max_len + 1);
+        (0..len).map(|_| alphabet[self.next(alphabet.len())]).collect()
+    }
+}
+
+#[test]
+fn test_rule_index_against_reference() {
+    let mut random = Random(20);
+    for _ in 0..300 {
+        let rules: Vec<(bool, String, Regex)> = (0..random.next(12) + 1)
+            .map(|_| {
+                let pattern = format!("/{}", random.string(&['/', 'a', 'b', '*', '$'], 5));
+                let regex = reference(&pattern);
+                (random.next(2) == 0, pattern, regex)
+            })
+            .collect();
+        let input = rules
+            .iter()
+            .fold(String::from("User-agent: *\n"), |input, (allowance, pattern, _)| {
+                let key = if *allowance { "Allow" } else { "Disallow" };
+                format!("{}{}: {}\n", input, key, pattern)
+            });
+        for evaluation_mode in [EvaluationMode::Legacy, EvaluationMode::Rfc9309].iter() {
+            let robots_txt = parse(&input, *evaluation_mode);
+            for _ in 0..20 {
+                let path = format!("/{}", random.string(&['/', 'a', 'b'], 6));
+                assert_eq!(
+                    robots_txt.can_fetch("*", &Url::parse(&format!("https://example.com{}", path)).unwrap()),
+                    reference_can_fetch(&rules, &path, *evaluation_mode),
+                    "mode {:?}, path `{}`, robots.txt:\n{}",
+                    evaluation_mode,
+                    path,
+                    input
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_rule_index_ties() {
+    let input = "User-agent: *\nDisallow: /page\nAllow: /page\nDisallow: /*.gif$\nAllow: /a.gif*";
+    let robots_txt = parse(input, EvaluationMode::Legacy);
+    assert!(!robots_txt.can_fetch("*", &url("/page")));
+    assert!(!robots_txt.can_fetch("*", &url("/a.gif")));
+    let robots_txt = parse(input, EvaluationMode::Rfc9309);
+    assert!(robots_txt.can_fetch("*", &url("/page")));
+    assert!(robots_txt.can_fetch("*", &url("/a.gif")));
+}
+
+#[test]
+fn test_rule_index_exact_patterns() {
+    let input = "User-agent: *\nAllow: /$\nDisallow: /\nAllow: /page$\nAllow: /dir*$";
+    let robots_txt = parse_robots_txt(origin(), input).get_result();
+    assert!(robots_txt.can_fetch("*", &url("/")));
+    assert!(robots_txt.can_fetch("*", &url("/page")));
+    assert!(!robots_txt.can_fetch("*", &url("/page/")));
+    assert!(!robots_txt.can_fetch("*", &url("/pag")));
+    assert!(robots_txt.can_fetch("*", &url("/dir")));
+    assert!(robots_txt.can_fetch("*", &url("/directory/page")));
+}
+
+#[test]
+fn test_rule_index_many_rules() {
+    let mut input = String::from("User-agent: *\nDisallow: /*.pdf$\n");
+    for i in 0..4000 {
+        input.push_str(&format!("Disallow: /private/{}/\nAllow: /private/{}/public\n", i, i));
+    }
+    for evaluation_mode in [EvaluationMode::Legacy, EvaluationMode::Rfc9309].iter() {
+        let robots_txt = parse(&input, *evaluation_mode);
+        assert!(robots_txt.can_fetch("*", &url("/private/")));
+        assert!(!robots_txt.can_fetch("*", &url("/private/3999/page")));
+        assert!(robots_txt.can_fetch("*", &url("/private/3999/public/page")));
+        assert!(robots_txt.can_fetch("*", &url("/private/3999/public/file.pdf")));
+        assert!(!robots_txt.can_fetch("*", &url("/private/4000/file.pdf")));
+        assert!(robots_txt.can_fetch("*", &url("/private/4000/page")));
+        assert!(!robots_txt.can_fetch("*", &url("/file.pdf")));
+    }
+}
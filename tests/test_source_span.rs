@@ -0,0 +1,90 @@
+mod common;
+
+use common::origin;
+use robotparser::model::{EvaluationMode, RobotsTxt};
+use robotparser::parser::{parse_robots_txt, parse_robots_txt_with_options, ParserOptions};
+use robotparser::service::RobotsTxtService;
+use url::Url;
+
+const DOC: &str = "\u{feff}# comment\r\n\
+User-agent: a\r\n\
+User-agent: b\r\n\
+Disallow: /private # secret\r\n\
+Crawl-delay: 5\r\n\
+\r\n\
+Sitemap: https://example.com/sitemap.xml\r\n\
+User-agent: *\r\n\
+Allow: /public\r\n\
+Request-rate: 1/10\r\n\
+Clean-param: /articles ref\r\n\
+\r\n\
+User-agent: a\r\n\
+Disallow: /tmp";
+
+/// Parses the document in the RFC 9309 mode, which merges the groups of the user agent `a`.
+fn parse_merged() -> RobotsTxt {
+    let options = ParserOptions::default().with_evaluation_mode(EvaluationMode::Rfc9309);
+    parse_robots_txt_with_options(origin(), DOC, &options).get_result()
+}
+
+#[test]
+fn test_group_spans() {
+    let robots_txt = parse_merged();
+    let groups = robots_txt.get_groups();
+    let group = groups.iter().find(|group| group.get_user_agents() == ["a"]).unwrap();
+    let spans = group.get_spans();
+    assert_eq!(spans.len(), 2);
+    assert_eq!(spans[0].get_line_no(), 2);
+    assert_eq!(spans[0].get_end_line_no(), 5);
+    assert_eq!(
+        &DOC[spans[0].get_range()],
+        "User-agent: a\r\nUser-agent: b\r\nDisallow: /private # secret\r\nCrawl-delay: 5"
+    );
+    assert_eq!(&DOC[spans[1].get_range()], "User-agent: a\r\nDisallow: /tmp");
+    let rules = group.get_rules();
+    assert_eq!(rules.len(), 2);
+    assert_eq!(rules[0].get_pattern(), "/private");
+    assert_eq!(rules[0].get_line_no(), Some(4));
+    assert_eq!(
+        &DOC[rules[0].get_span().unwrap().get_range()],
+        "Disallow: /private # secret"
+    );
+    assert_eq!(rules[1].get_line_no(), Some(14));
+    let span = group.get_crawl_delay_span().unwrap();
+    assert_eq!(span.get_line_no(), 5);
+    assert_eq!(&DOC[span.get_range()], "Crawl-delay: 5");
+    assert!(group.get_req_rate_span().is_none());
+    let group = groups.iter().find(|group| group.get_user_agents() == ["b"]).unwrap();
+    assert_eq!(group.get_spans().len(), 1);
+    let group = groups.iter().find(|group| group.get_user_agents() == ["*"]).unwrap();
+    let span = group.get_req_rate_span().unwrap();
+    assert_eq!(span.get_line_no(), 10);
+    assert_eq!(&DOC[span.get_range()], "Request-rate: 1/10");
+    assert_eq!(group.get_spans()[0].get_end_line_no(), 10);
+}
+
+#[test]
+fn test_directive_spans() {
+    let robots_txt = parse_robots_txt(origin(), DOC).get_result();
+    assert_eq!(robots_txt.get_sitemaps().len(), 1);
+    let span = robots_txt.get_sitemap_spans()[0];
+    assert_eq!(span.get_line_no(), 7);
+    assert_eq!(&DOC[span.get_range()], "Sitemap: https://example.com/sitemap.xml");
+    let clean_params = &robots_txt.get_clean_params()[0];
+    assert_eq!(clean_params.get_params(), &vec![String::from("ref")]);
+    assert_eq!(clean_params.get_pattern(), "/articles");
+    let span = clean_params.get_span().unwrap();
+    assert_eq!(span.get_line_no(), 11);
+    assert_eq!(&DOC[span.get_range()], "Clean-param: /articles ref");
+}
+
+#[test]
+fn test_explain_spans() {
+    let robots_txt = parse_merged();
+    let decision = robots_txt.explain("a", &Url::parse("https://example.com/tmp/file").unwrap());
+    assert!(!decision.is_allowed());
+    assert_eq!(decision.get_line_no(), Some(14));
+    let span = decision.get_rule().unwrap().get_span().unwrap();
+    assert_eq!(&DOC[span.get_range()], "Disallow: /tmp");
+    assert_eq!(decision.get_group_spans().len(), 2);
+}
@@ -0,0 +1,191 @@
+mod common;
+
+use common::{TestResponse, TestServer};
+use robotparser::http::RobotsTxtClient;
+use robotparser::model::{ErrorClass, FetchPolicy, RetryPolicy, SystemClock};
+use robotparser::parser::{parse_fetched_robots_txt, parse_fetched_robots_txt_with_policy};
+use robotparser::service::RobotsTxtService;
+use std::net::TcpListener;
+use std::time::Duration;
+use tokio::runtime::Runtime;
+use url::{Origin, Url};
+
+const DOC: &str = "User-agent: *\nDisallow: /private";
+
+fn fast_retries(max_retries: u32) -> RetryPolicy {
+    RetryPolicy::new(max_retries)
+        .with_initial_backoff(Duration::from_millis(1))
+        .with_max_backoff(Duration::from_millis(10))
+}
+
+/// Returns the origin of a port that does not accept connections.
+fn closed_origin() -> Origin {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+    drop(listener);
+    url.origin()
+}
+
+#[test]
+fn test_retry_policy_backoff() {
+    let retry_policy = RetryPolicy::new(5)
+        .with_initial_backoff(Duration::from_secs(1))
+        .with_max_backoff(Duration::from_secs(10));
+    assert_eq!(retry_policy.get_max_retries(), 5);
+    assert_eq!(retry_policy.get_backoff(1, None), Duration::from_secs(1));
+    assert_eq!(retry_policy.get_backoff(2, None), Duration::from_secs(2));
+    assert_eq!(retry_policy.get_backoff(3, None), Duration::from_secs(4));
+    assert_eq!(retry_policy.get_backoff(5, None), Duration::from_secs(10));
+    assert_eq!(retry_policy.get_backoff(100, None), Duration::from_secs(10));
+    assert_eq!(
+        retry_policy.get_backoff(1, Some(Duration::from_secs(3))),
+        Duration::from_secs(3)
+    );
+    assert_eq!(
+        retry_policy.get_backoff(1, Some(Duration::from_secs(60))),
+        Duration::from_secs(10)
+    );
+    assert_eq!(RetryPolicy::default().get_max_retries(), 0);
+}
+
+#[test]
+fn test_unreachable_disallows_everything() {
+    let url = Url::parse("https://www.example.com/robots.txt").unwrap();
+    let robots_txt = parse_fetched_robots_txt_with_policy(url.origin(), 503, DOC, &FetchPolicy::rfc9309()).get_result();
+    assert!(robots_txt.is_unreachable());
+    assert!(robots_txt.get_unreachable_since().is_some());
+    assert!(!robots_txt.is_unreachable_timeout_elapsed(&SystemClock));
+    assert!(!robots_txt.can_fetch("foobot", &url.join("/public").unwrap()));
+}
+
+#[test]
+fn test_unreachable_after_timeout() {
+    let url = Url::parse("https://www.example.com/robots.txt").unwrap();
+    let policy = FetchPolicy::rfc9309().with_unreachable_timeout(Duration::from_secs(0));
+    let public_url = url.join("/public").unwrap();
+    let private_url = url.join("/private").unwrap();
+
+    // Without a cached copy everything is allowed.
+    let robots_txt = parse_fetched_robots_txt_with_policy(url.origin(), 503, DOC, &policy).get_result();
+    assert!(robots_txt.is_unreachable_timeout_elapsed(&SystemClock));
+    assert!(robots_txt.can_fetch("foobot", &public_url));
+    assert!(robots_txt.can_fetch("foobot", &private_url));
+
+    // With a cached copy its rules are used.
+    let previous = parse_fetched_robots_txt(url.origin(), 200, DOC).get_result();
+    let robots_txt = robots_txt.with_previous(&previous);
+    assert!(robots_txt.can_fetch("foobot", &public_url));
+    assert!(!robots_txt.can_fetch("foobot", &private_url));
+
+    // The cached copy and the start of the outage survive further failures.
+    let since = robots_txt.get_unreachable_since();
+    let next = parse_fetched_robots_txt_with_policy(url.origin(), 500, DOC, &policy)
+        .get_result()
+        .with_previous(&robots_txt);
+    assert_eq!(next.get_unreachable_since(), since);
+    assert!(!next.can_fetch("foobot", &private_url));
+
+    // A successful fetch is not affected by the previous outage.
+    let fetched = parse_fetched_robots_txt(url.origin(), 200, "")
+        .get_result()
+        .with_previous(&next);
+    assert!(!fetched.is_unreachable());
+    assert!(fetched.can_fetch("foobot", &private_url));
+}
+
+#[test]
+fn test_blocking_retry_after_server_error() {
+    let server = TestServer::start(vec![
+        TestResponse::new(503, "").with_header("Retry-After", "0"),
+        TestResponse::new(429, "").with_header("Retry-After", "Sun, 06 Nov 1994 08:49:37 GMT"),
+        TestResponse::new(200, DOC),
+    ]);
+    let client = reqwest::blocking::Client::new();
+    let policy = FetchPolicy::rfc9309().with_retry_policy(fast_retries(2));
+    let robots_txt = client
+        .fetch_robots_txt_with_policy(server.origin(), &policy)
+        .unwrap()
+        .get_result();
+    assert_eq!(server.requests().len(), 3);
+    assert!(robots_txt.can_fetch("foobot", &server.url("/public")));
+    assert!(!robots_txt.can_fetch("foobot", &server.url("/private")));
+}
+
+#[test]
+fn test_blocking_retry_after_out_of_range_date() {
+    let server = TestServer::start(vec![
+        TestResponse::new(503, "").with_header("Retry-After", "Sun, 06 Nov 999999999999999 08:49:37 GMT"),
+        TestResponse::new(200, DOC),
+    ]);
+    let client = reqwest::blocking::Client::new();
+    let policy = FetchPolicy::rfc9309().with_retry_policy(fast_retries(1));
+    let robots_txt = client
+        .fetch_robots_txt_with_policy(server.origin(), &policy)
+        .unwrap()
+        .get_result();
+    assert_eq!(server.requests().len(), 2);
+    assert!(!robots_txt.can_fetch("foobot", &server.url("/private")));
+}
+
+#[test]
+fn test_blocking_retries_exhausted() {
+    let server = TestServer::start(vec![TestResponse::new(500, "")]);
+    let client = reqwest::blocking::Client::new();
+    let policy = FetchPolicy::rfc9309().with_retry_policy(fast_retries(1));
+    let robots_txt = client
+        .fetch_robots_txt_with_policy(server.origin(), &policy)
+        .unwrap()
+        .get_result();
+    assert_eq!(server.requests().len(), 2);
+    assert!(robots_txt.is_unreachable());
+    assert!(!robots_txt.can_fetch("foobot", &server.url("/public")));
+}
+
+#[test]
+fn test_blocking_network_error() {
+    let client = reqwest::blocking::Client::new();
+    let origin = closed_origin();
+    let err = client.fetch_robots_txt(origin.clone()).unwrap_err();
+    assert_eq!(err.get_class(), ErrorClass::Connection);
+    assert!(err.is_transient());
+
+    let policy = FetchPolicy::rfc9309().with_retry_policy(fast_retries(1));
+    let robots_txt = client
+        .fetch_robots_txt_with_policy(origin, &policy)
+        .unwrap()
+        .get_result();
+    assert!(robots_txt.is_unreachable());
+}
+
+#[test]
+fn test_async_retry_after_server_error() {
+    let server = TestServer::start(vec![
+        TestResponse::new(502, "").with_header("Retry-After", "0"),
+        TestResponse::new(200, DOC),
+    ]);
+    let runtime = Runtime::new().unwrap();
+    let client = reqwest::Client::new();
+    let policy = FetchPolicy::rfc9309().with_retry_policy(fast_retries(2));
+    let robots_txt = runtime
+        .block_on(client.fetch_robots_txt_with_policy(server.origin(), &policy).unwrap())
+        .unwrap()
+        .get_result();
+    assert_eq!(server.requests().len(), 2);
+    assert!(!robots_txt.can_fetch("foobot", &server.url("/private")));
+}
+
+#[test]
+fn test_async_network_error() {
+    let runtime = Runtime::new().unwrap();
+    let client = reqwest::Client::new();
+    let origin = closed_origin();
+    let result = runtime.block_on(client.fetch_robots_txt(origin.clone()).unwrap());
+    assert!(result.unwrap_err().is_transient());
+
+    let policy = FetchPolicy::google().with_retry_policy(fast_retries(1));
+    let robots_txt = runtime
+        .block_on(client.fetch_robots_txt_with_policy(origin, &policy).unwrap())
+        .unwrap()
+        .get_result();
+    assert!(robots_txt.is_unreachable());
+}
@@ -0,0 +1,126 @@
+use robotparser::model::{EvaluationMode, ProductTokenMatcher};
+use robotparser::parser::parse_robots_txt;
+use robotparser::service::RobotsTxtService;
+use robotparser::user_agent::{extract_product_tokens, AgentFamilies};
+use url::Url;
+
+#[test]
+fn test_extract_product_tokens() {
+    let cases: &[(&str, &[&str])] = &[
+        ("", &[]),
+        ("*", &["*"]),
+        ("Googlebot", &["googlebot"]),
+        ("Googlebot/2.1", &["googlebot"]),
+        ("Googlebot/2.1 (+http://www.google.com/bot.html)", &["googlebot"]),
+        (
+            "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)",
+            &["googlebot", "mozilla"],
+        ),
+        (
+            "Mozilla/5.0 (compatible; bingbot/2.0; +http://www.bing.com/bingbot.htm)",
+            &["bingbot", "mozilla"],
+        ),
+        (
+            "Mozilla/5.0 AppleWebKit/537.36 (KHTML, like Gecko; compatible; Googlebot/2.1; +http://www.google.com/bot.html) Chrome/99.0.4844.84 Safari/537.36",
+            &["googlebot", "mozilla", "applewebkit", "chrome", "safari"],
+        ),
+        (
+            "Mozilla/5.0 (Linux; Android 6.0.1; Nexus 5X Build/MMB29P) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/99.0.4844.84 Mobile Safari/537.36 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)",
+            &["googlebot", "mozilla", "applewebkit", "chrome", "mobile", "safari"],
+        ),
+        ("FigTree Robot libwww-perl/5.04", &["figtree", "robot", "libwww-perl"]),
+        ("DuckDuckBot/1.1; (+http://duckduckgo.com/duckduckbot.html)", &["duckduckbot"]),
+        ("Mozilla/5.0 (compatible; YandexBot/3.0", &["yandexbot", "mozilla"]),
+        ("foo.bot", &["foo.bot"]),
+        ("Foo.Bot/1.0", &["foo.bot"]),
+    ];
+    for (user_agent, expected_tokens) in cases.iter() {
+        assert_eq!(&extract_product_tokens(user_agent), expected_tokens, "{}", user_agent);
+    }
+}
+
+#[test]
+fn test_can_fetch_with_user_agent_header() {
+    let doc = "\n\
+    User-agent: *\n\
+    Disallow: /\n\
+    \n\
+    User-agent: Googlebot\n\
+    Disallow: /private\n\
+    ";
+    let url = Url::parse("https://www.example.com/robots.txt").unwrap();
+    let mut robots_txt = parse_robots_txt(url.origin(), doc).get_result();
+    let header = "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+    let public_url = Url::parse("https://www.example.com/public").unwrap();
+    let private_url = Url::parse("https://www.example.com/private").unwrap();
+    robots_txt.set_user_agent_matcher(ProductTokenMatcher);
+    for url in [&public_url, &private_url].iter() {
+        assert_eq!(
+            robots_txt.can_fetch(header, url),
+            robots_txt.can_fetch("Googlebot", url)
+        );
+    }
+    robots_txt.set_evaluation_mode(EvaluationMode::Rfc9309);
+    assert!(robots_txt.can_fetch(header, &public_url));
+    assert!(!robots_txt.can_fetch(header, &private_url));
+    assert!(robots_txt.can_fetch("Googlebot", &public_url));
+    assert!(!robots_txt.can_fetch("Mozilla/5.0 (compatible; Bingbot/2.0)", &public_url));
+}
+
+#[test]
+fn test_can_fetch_without_product_token() {
+    let url = Url::parse("https://www.example.com/robots.txt").unwrap();
+    let robots_txt = parse_robots_txt(url.origin(), "User-agent: foo.bot\nDisallow: /\n").get_result();
+    let page_url = Url::parse("https://www.example.com/page").unwrap();
+    assert!(!robots_txt.can_fetch("foo.bot", &page_url));
+    assert!(!robots_txt.can_fetch("foo.bot/1.0", &page_url));
+    assert!(robots_txt.can_fetch("bar.bot", &page_url));
+}
+
+#[test]
+fn test_agent_families_expand() {
+    let families = AgentFamilies::new()
+        .with_family("Foobot-Image", &["Foobot-Media", "Foobot"])
+        .with_family("Foobot-Media", &["Foobot"]);
+    let tokens = vec!["foobot-image".to_string(), "mozilla".to_string()];
+    assert_eq!(
+        families.expand(&tokens),
+        vec!["foobot-image", "foobot-media", "foobot", "mozilla"]
+    );
+    assert_eq!(families.get_fallback_tokens("FOOBOT-MEDIA"), &["foobot".to_string()]);
+    assert!(families.get_fallback_tokens("barbot").is_empty());
+    assert_eq!(
+        AgentFamilies::builtin().get_fallback_tokens("Googlebot-Image"),
+        &["googlebot".to_string()]
+    );
+}
+
+#[test]
+fn test_agent_families_fallback() {
+    let doc = "\n\
+    User-agent: *\n\
+    Disallow: /\n\
+    \n\
+    User-agent: Googlebot\n\
+    Disallow: /private\n\
+    \n\
+    User-agent: Googlebot-News\n\
+    Disallow: /archive\n\
+    ";
+    let url = Url::parse("https://www.example.com/robots.txt").unwrap();
+    let mut robots_txt = parse_robots_txt(url.origin(), doc).get_result();
+    robots_txt.set_evaluation_mode(EvaluationMode::Rfc9309);
+    robots_txt.set_user_agent_matcher(ProductTokenMatcher);
+    let public_url = Url::parse("https://www.example.com/public").unwrap();
+    let private_url = Url::parse("https://www.example.com/private").unwrap();
+    let archive_url = Url::parse("https://www.example.com/archive").unwrap();
+    assert!(!robots_txt.can_fetch("Googlebot-Image/1.0", &public_url));
+
+    robots_txt.set_agent_families(AgentFamilies::google());
+    assert!(robots_txt.can_fetch("Googlebot-Image/1.0", &public_url));
+    assert!(!robots_txt.can_fetch("Googlebot-Image/1.0", &private_url));
+    assert!(robots_txt.can_fetch("Googlebot-Image/1.0", &archive_url));
+    assert!(robots_txt.can_fetch("Googlebot-News", &private_url));
+    assert!(!robots_txt.can_fetch("Googlebot-News", &archive_url));
+    assert!(!robots_txt.can_fetch("Bingbot", &public_url));
+}
@@ -0,0 +1,109 @@
+use robotparser::model::UserAgentMatcher;
+use robotparser::model::{EvaluationMode, PrefixMatcher, ProductTokenMatcher, RobotsTxt, SubstringMatcher};
+use robotparser::parser::parse_robots_txt;
+use robotparser::service::RobotsTxtService;
+use url::Url;
+
+const DOC: &str = "\n\
+User-agent: *\n\
+Disallow: /default\n\
+\n\
+User-agent: googlebot\n\
+Disallow: /google\n\
+\n\
+User-agent: bot\n\
+Disallow: /bot\n\
+";
+
+fn parse(doc: &str) -> RobotsTxt {
+    let url = Url::parse("https://www.example.com/robots.txt").unwrap();
+    parse_robots_txt(url.origin(), doc).get_result()
+}
+
+fn can_fetch(robots_txt: &RobotsTxt, agent: &str, path: &str) -> bool {
+    let url = Url::parse(&format!("https://www.example.com{}", path)).unwrap();
+    robots_txt.can_fetch(agent, &url)
+}
+
+#[test]
+fn test_builtin_matchers() {
+    assert!(ProductTokenMatcher.matches("googlebot", "googlebot"));
+    assert!(!ProductTokenMatcher.matches("googlebot", "googlebot-news"));
+    assert!(!ProductTokenMatcher.matches("bot", "googlebot"));
+    assert!(PrefixMatcher.matches("googlebot", "googlebot-news"));
+    assert!(!PrefixMatcher.matches("bot", "googlebot"));
+    assert!(SubstringMatcher.matches("bot", "googlebot"));
+    assert!(!SubstringMatcher.matches("googlebot-news", "googlebot"));
+}
+
+#[test]
+fn test_default_matcher_is_substring() {
+    let robots_txt = parse(DOC);
+    assert!(!can_fetch(&robots_txt, "Googlebot-News", "/google"));
+    assert!(!can_fetch(&robots_txt, "Bingbot", "/bot"));
+    assert!(!can_fetch(&robots_txt, "Bingbot", "/default"));
+}
+
+#[test]
+fn test_product_token_matcher() {
+    let mut robots_txt = parse(DOC);
+    robots_txt.set_user_agent_matcher(ProductTokenMatcher);
+    assert!(!can_fetch(&robots_txt, "Googlebot/2.1", "/google"));
+    assert!(can_fetch(&robots_txt, "Googlebot-News", "/google"));
+    assert!(!can_fetch(&robots_txt, "Googlebot-News", "/default"));
+    assert!(can_fetch(&robots_txt, "Bingbot", "/bot"));
+    assert!(!can_fetch(&robots_txt, "Bingbot", "/default"));
+}
+
+#[test]
+fn test_prefix_matcher() {
+    let mut robots_txt = parse(DOC);
+    robots_txt.set_user_agent_matcher(PrefixMatcher);
+    assert!(!can_fetch(&robots_txt, "Googlebot-News", "/google"));
+    assert!(can_fetch(&robots_txt, "Bingbot", "/bot"));
+    assert!(!can_fetch(&robots_txt, "Bot-Crawler", "/bot"));
+}
+
+#[test]
+fn test_default_matcher_in_rfc9309_mode() {
+    let mut robots_txt = parse(DOC);
+    robots_txt.set_evaluation_mode(EvaluationMode::Rfc9309);
+    assert!(!robots_txt.get_user_agent_matcher().matches("bot", "googlebot"));
+    // The `bot` group does not apply to `Googlebot`, and the `googlebot` group is matched case-insensitively.
+    assert!(can_fetch(&robots_txt, "Googlebot/2.1", "/bot"));
+    assert!(!can_fetch(&robots_txt, "Googlebot/2.1", "/google"));
+    assert!(can_fetch(&robots_txt, "Googlebot/2.1", "/default"));
+    assert!(!can_fetch(&robots_txt, "Bot", "/bot"));
+    assert!(!can_fetch(&robots_txt, "Bingbot", "/default"));
+}
+
+#[test]
+fn test_matcher_in_rfc9309_mode() {
+    let mut robots_txt = parse(DOC);
+    robots_txt.set_evaluation_mode(EvaluationMode::Rfc9309);
+    robots_txt.set_user_agent_matcher(ProductTokenMatcher);
+    assert!(can_fetch(&robots_txt, "Googlebot-News", "/google"));
+    assert!(!can_fetch(&robots_txt, "Googlebot-News", "/default"));
+    robots_txt.set_user_agent_matcher(SubstringMatcher);
+    // `googlebot` is more specific than `bot`.
+    assert!(!can_fetch(&robots_txt, "Googlebot-News", "/google"));
+    assert!(can_fetch(&robots_txt, "Googlebot-News", "/bot"));
+}
+
+#[derive(Debug)]
+struct SuffixMatcher;
+
+impl UserAgentMatcher for SuffixMatcher {
+    fn matches(&self, group_user_agent: &str, user_agent: &str) -> bool {
+        user_agent.ends_with(group_user_agent)
+    }
+}
+
+#[test]
+fn test_custom_matcher() {
+    let mut robots_txt = parse(DOC);
+    robots_txt.set_user_agent_matcher(SuffixMatcher);
+    assert!(!can_fetch(&robots_txt, "Bingbot", "/bot"));
+    assert!(can_fetch(&robots_txt, "Bot-Crawler", "/bot"));
+    assert!(!robots_txt.get_user_agent_matcher().matches("bot", "bot-crawler"));
+}
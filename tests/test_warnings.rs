@@ -1,4 +1,5 @@
-use robotparser::parser::{parse_robots_txt, WarningReason};
+use robotparser::model::EvaluationMode;
+use robotparser::parser::{parse_robots_txt, parse_robots_txt_with_options, ParserOptions, WarningReason};
 use std::convert::From;
 use url::{Host, Origin};
 
@@ -16,6 +17,14 @@ enum WarningReasonKind {
     WrongCleanParamFormat,
     IgnoredCleanParams,
     WrongPathFormat,
+    GroupMerged,
+    BodyTruncated,
+    TooManyLines,
+    TooManyRules,
+    TooManyGroups,
+    TooManyRulesInTotal,
+    PatternTooLong,
+    TooManyWildcards,
 }
 
 fn validate_warnings(input: &str, expected_warnings: &[WarningReasonKind]) {
@@ -133,6 +142,35 @@ fn test_warning_wrong_path_format() {
     validate_warnings(input, &[WarningReasonKind::WrongPathFormat]);
 }
 
+#[test]
+fn test_warning_group_merged() {
+    let input = "User-Agent: a\nDisallow: /\nUser-Agent: b\nDisallow: /\nUser-Agent: A\nDisallow: /";
+    // The records are merged only in the RFC 9309 mode.
+    validate_warnings(input, &[]);
+    let host = Host::Domain("python.org".into());
+    let origin = Origin::Tuple("http".into(), host, 80);
+    let options = ParserOptions::default().with_evaluation_mode(EvaluationMode::Rfc9309);
+    let warnings = parse_robots_txt_with_options(origin.clone(), input, &options)
+        .get_warnings()
+        .to_vec();
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].get_line_no(), 5);
+    match warnings[0].get_reason() {
+        WarningReason::GroupMerged {
+            user_agent,
+            first_line_no,
+        } => {
+            assert_eq!(user_agent, "a");
+            assert_eq!(*first_line_no, 1);
+        }
+        reason => panic!("Unexpected warning reason: {}", reason),
+    }
+    let input = "User-Agent: a\nUser-Agent: a\nDisallow: /";
+    assert!(parse_robots_txt_with_options(origin, input, &options)
+        .get_warnings()
+        .is_empty());
+}
+
 impl From<&WarningReason> for WarningReasonKind {
     fn from(reason: &WarningReason) -> Self {
         match *reason {
@@ -148,6 +186,14 @@ impl From<&WarningReason> for WarningReasonKind {
             WarningReason::WrongCleanParamFormat => WarningReasonKind::WrongCleanParamFormat,
             WarningReason::IgnoredCleanParams { .. } => WarningReasonKind::IgnoredCleanParams,
             WarningReason::WrongPathFormat => WarningReasonKind::WrongPathFormat,
+            WarningReason::GroupMerged { .. } => WarningReasonKind::GroupMerged,
+            WarningReason::BodyTruncated { .. } => WarningReasonKind::BodyTruncated,
+            WarningReason::TooManyLines { .. } => WarningReasonKind::TooManyLines,
+            WarningReason::TooManyRules { .. } => WarningReasonKind::TooManyRules,
+            WarningReason::TooManyGroups { .. } => WarningReasonKind::TooManyGroups,
+            WarningReason::TooManyRulesInTotal { .. } => WarningReasonKind::TooManyRulesInTotal,
+            WarningReason::PatternTooLong { .. } => WarningReasonKind::PatternTooLong,
+            WarningReason::TooManyWildcards { .. } => WarningReasonKind::TooManyWildcards,
         }
     }
 }